@@ -0,0 +1,51 @@
+//! Compare the sequential and parallel line graph construction
+//!
+//! Run with `cargo bench --features rayon`.
+use line_graph::{line_graph, par_line_graph};
+use petgraph::graph::UnGraph;
+use std::time::{Duration, Instant};
+
+const NODES: u32 = 100_000;
+const EDGES: usize = 400_000;
+const RUNS: usize = 10;
+
+fn random_graph(nodes: u32, edges: usize) -> UnGraph<String, usize> {
+    let mut state = 1u64;
+    let mut next = || {
+        state = state
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        ((state >> 33) % nodes as u64) as u32
+    };
+    let mut g = UnGraph::with_capacity(nodes as usize, edges);
+    for n in 0..nodes {
+        g.add_node(format!("vertex {n}"));
+    }
+    for e in 0..edges {
+        let (a, b) = (next(), next());
+        g.add_edge(a.into(), b.into(), e);
+    }
+    g
+}
+
+fn fastest<T>(mut f: impl FnMut() -> T) -> Duration {
+    (0..RUNS)
+        .map(|_| {
+            let start = Instant::now();
+            std::hint::black_box(f());
+            start.elapsed()
+        })
+        .min()
+        .unwrap()
+}
+
+fn main() {
+    let g = random_graph(NODES, EDGES);
+    let seq = fastest(|| line_graph(&g));
+    let par = fastest(|| par_line_graph(&g));
+    println!("{NODES} vertices, {EDGES} edges, {RUNS} runs each");
+    println!(
+        "{} threads, fastest run: line_graph {seq:?}, par_line_graph {par:?}",
+        rayon::current_num_threads()
+    );
+}
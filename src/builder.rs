@@ -0,0 +1,383 @@
+use crate::{
+    build_line_graph, build_line_graph_with_map, build_simple_line_graph,
+    size::{check_size, degree, line_graph_size},
+    InputIx, LineGraphEdgeRef, LineGraphError, LineGraphIndex, LineGraphMap,
+};
+
+use petgraph::{
+    graph::{EdgeIndex, IndexType, UnGraph},
+    visit::{
+        EdgeIndexable, EdgeRef, GraphProp, IntoEdgeReferences,
+        IntoNodeReferences, NodeIndexable, NodeRef,
+    },
+    Undirected,
+};
+use std::marker::PhantomData;
+
+/// How to treat self-loops in the original graph
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum LoopPolicy {
+    /// Loops are not incident to any vertex
+    ///
+    /// Each loop becomes an isolated node in the line graph.
+    Ignore,
+    /// A loop is incident to its vertex once
+    ///
+    /// The loop is adjacent to each other edge at its vertex once. This
+    /// is the default.
+    #[default]
+    Once,
+    /// A loop is incident to its vertex twice
+    ///
+    /// The loop is adjacent to each other edge at its vertex twice and
+    /// to itself, so that it becomes a self-loop in the line graph.
+    Twice,
+}
+
+/// Construct line graphs with non-default settings
+///
+/// By default, the line graph has the index type of the original
+/// graph, see [InputIx]. A different index type can be chosen with
+/// [index_type](Self::index_type).
+///
+/// # Example
+///
+/// ```rust
+/// use line_graph::{LineGraphBuilder, LoopPolicy};
+/// use petgraph::graph::UnGraph;
+///
+/// // a tadpole
+/// let g = UnGraph::<(), ()>::from_edges([(0, 1), (1, 1)]);
+/// let g_line = LineGraphBuilder::new()
+///     .loops(LoopPolicy::Twice)
+///     .build(&g);
+/// assert_eq!(g_line.edge_count(), 3);
+/// assert!(g_line.contains_edge(1.into(), 1.into()));
+///
+/// let g_line = LineGraphBuilder::new().index_type::<u16>().build(&g);
+/// let _: UnGraph<(), (), u16> = g_line;
+/// ```
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct LineGraphBuilder<Ix = InputIx> {
+    loops: LoopPolicy,
+    index_type: PhantomData<Ix>,
+}
+
+impl LineGraphBuilder {
+    /// Create a builder with default settings
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<Ix> LineGraphBuilder<Ix> {
+    /// Set how to treat self-loops
+    pub fn loops(mut self, loops: LoopPolicy) -> Self {
+        self.loops = loops;
+        self
+    }
+
+    /// Set the index type of the line graph
+    ///
+    /// Constructing a line graph with more nodes or edges than `NewIx`
+    /// can index panics. Use [try_build](Self::try_build) to check the
+    /// size before construction.
+    pub fn index_type<NewIx: IndexType>(self) -> LineGraphBuilder<NewIx> {
+        LineGraphBuilder {
+            loops: self.loops,
+            index_type: PhantomData,
+        }
+    }
+
+    /// The number of edges in the line graph of `g`, see
+    /// [line_graph_edge_count](crate::line_graph_edge_count)
+    pub fn edge_count<G>(&self, g: G) -> usize
+    where
+        G: IntoEdgeReferences + NodeIndexable,
+        G::EdgeRef: LineGraphEdgeRef,
+    {
+        line_graph_size(g, self.loops).edges
+    }
+
+    /// The degree of the line graph node corresponding to the edge `e`
+    /// of `g`, see [line_graph_degree](crate::line_graph_degree)
+    ///
+    /// A line graph self-loop counts twice towards the degree.
+    pub fn degree<N, E, GIx: IndexType>(
+        &self,
+        g: &UnGraph<N, E, GIx>,
+        e: EdgeIndex<GIx>,
+    ) -> Option<usize> {
+        degree(g, e, self.loops)
+    }
+
+    /// Construct the line graph for `g`, see [line_graph](crate::line_graph)
+    ///
+    /// # Panics
+    ///
+    /// Panics before allocating the line graph if it has more nodes or
+    /// edges than `Ix` can index.
+    pub fn build<G>(
+        &self,
+        g: G,
+    ) -> UnGraph<
+        G::EdgeWeight,
+        G::NodeWeight,
+        <Ix as LineGraphIndex<G::EdgeRef>>::Ix,
+    >
+    where
+        G: IntoNodeReferences
+            + IntoEdgeReferences
+            + NodeIndexable
+            + GraphProp<EdgeType = Undirected>,
+        G::EdgeRef: LineGraphEdgeRef,
+        Ix: LineGraphIndex<G::EdgeRef>,
+        G::NodeWeight: Clone,
+        G::EdgeWeight: Clone,
+    {
+        build_line_graph(
+            g,
+            self.loops,
+            |e| e.weight().clone(),
+            |_, _, v| v.weight().clone(),
+        )
+    }
+
+    /// Construct the line graph for `g` if it is not too large, see
+    /// [try_line_graph](crate::try_line_graph)
+    ///
+    /// Returns an error if the line graph would have more than `budget`
+    /// edges or more nodes or edges than can be indexed with `Ix`.
+    #[allow(clippy::type_complexity)]
+    pub fn try_build<G>(
+        &self,
+        g: G,
+        budget: usize,
+    ) -> Result<
+        UnGraph<
+            G::EdgeWeight,
+            G::NodeWeight,
+            <Ix as LineGraphIndex<G::EdgeRef>>::Ix,
+        >,
+        LineGraphError,
+    >
+    where
+        G: IntoNodeReferences
+            + IntoEdgeReferences
+            + NodeIndexable
+            + GraphProp<EdgeType = Undirected>,
+        G::EdgeRef: LineGraphEdgeRef,
+        Ix: LineGraphIndex<G::EdgeRef>,
+        G::NodeWeight: Clone,
+        G::EdgeWeight: Clone,
+    {
+        check_size::<<Ix as LineGraphIndex<G::EdgeRef>>::Ix>(
+            line_graph_size(g, self.loops),
+            budget,
+        )?;
+        Ok(self.build(g))
+    }
+
+    /// Construct the line graph for `g` together with the
+    /// correspondence between the line graph and `g`, see
+    /// [line_graph_with_map](crate::line_graph_with_map)
+    #[allow(clippy::type_complexity)]
+    pub fn build_with_map<G>(
+        &self,
+        g: G,
+    ) -> (
+        UnGraph<
+            G::EdgeWeight,
+            G::NodeWeight,
+            <Ix as LineGraphIndex<G::EdgeRef>>::Ix,
+        >,
+        LineGraphMap<
+            G::NodeId,
+            G::EdgeId,
+            <Ix as LineGraphIndex<G::EdgeRef>>::Ix,
+        >,
+    )
+    where
+        G: IntoNodeReferences
+            + IntoEdgeReferences
+            + NodeIndexable
+            + EdgeIndexable
+            + GraphProp<EdgeType = Undirected>,
+        G::EdgeRef: LineGraphEdgeRef,
+        Ix: LineGraphIndex<G::EdgeRef>,
+        G::NodeWeight: Clone,
+        G::EdgeWeight: Clone,
+    {
+        build_line_graph_with_map(
+            g,
+            self.loops,
+            |e| e.weight().clone(),
+            |_, _, v| v.weight().clone(),
+        )
+    }
+
+    /// Construct the line graph for `g` with custom weights, see
+    /// [line_graph_with](crate::line_graph_with)
+    pub fn build_with<G, NW, EW, NF, EF>(
+        &self,
+        g: G,
+        node_fn: NF,
+        edge_fn: EF,
+    ) -> UnGraph<NW, EW, <Ix as LineGraphIndex<G::EdgeRef>>::Ix>
+    where
+        G: IntoNodeReferences
+            + IntoEdgeReferences
+            + NodeIndexable
+            + GraphProp<EdgeType = Undirected>,
+        G::EdgeRef: LineGraphEdgeRef,
+        Ix: LineGraphIndex<G::EdgeRef>,
+        NF: FnMut(G::EdgeRef) -> NW,
+        EF: FnMut(G::EdgeRef, G::EdgeRef, G::NodeRef) -> EW,
+    {
+        build_line_graph(g, self.loops, node_fn, edge_fn)
+    }
+
+    /// Construct the simple line graph for `g`, see
+    /// [simple_line_graph](crate::simple_line_graph)
+    #[allow(clippy::type_complexity)]
+    pub fn build_simple<G>(
+        &self,
+        g: G,
+    ) -> UnGraph<
+        G::EdgeWeight,
+        Vec<G::NodeWeight>,
+        <Ix as LineGraphIndex<G::EdgeRef>>::Ix,
+    >
+    where
+        G: IntoNodeReferences
+            + IntoEdgeReferences
+            + NodeIndexable
+            + GraphProp<EdgeType = Undirected>,
+        G::EdgeRef: LineGraphEdgeRef,
+        Ix: LineGraphIndex<G::EdgeRef>,
+        G::NodeWeight: Clone,
+        G::EdgeWeight: Clone,
+    {
+        self.build_simple_with(
+            g,
+            |e| e.weight().clone(),
+            |_, _, shared| shared.iter().map(|v| v.weight().clone()).collect(),
+        )
+    }
+
+    /// Construct the simple line graph for `g` with custom weights,
+    /// see [simple_line_graph_with](crate::simple_line_graph_with)
+    pub fn build_simple_with<G, NW, EW, NF, EF>(
+        &self,
+        g: G,
+        node_fn: NF,
+        edge_fn: EF,
+    ) -> UnGraph<NW, EW, <Ix as LineGraphIndex<G::EdgeRef>>::Ix>
+    where
+        G: IntoNodeReferences
+            + IntoEdgeReferences
+            + NodeIndexable
+            + GraphProp<EdgeType = Undirected>,
+        G::EdgeRef: LineGraphEdgeRef,
+        Ix: LineGraphIndex<G::EdgeRef>,
+        NF: FnMut(G::EdgeRef) -> NW,
+        EF: FnMut(G::EdgeRef, G::EdgeRef, &[G::NodeRef]) -> EW,
+    {
+        build_simple_line_graph(g, self.loops, node_fn, edge_fn)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use petgraph::algo::is_isomorphic;
+
+    fn tadpole() -> UnGraph<(), ()> {
+        UnGraph::from_edges([(0, 1), (1, 1), (1, 2)])
+    }
+
+    #[test]
+    #[should_panic(expected = "exceeds the maximum index")]
+    fn index_overflow() {
+        let star = UnGraph::<(), ()>::from_edges((1..30).map(|n| (0, n)));
+        LineGraphBuilder::new().index_type::<u8>().build(&star);
+    }
+
+    #[test]
+    fn loops_ignore() {
+        let line = LineGraphBuilder::new()
+            .loops(LoopPolicy::Ignore)
+            .build(&tadpole());
+        let target = UnGraph::<(), ()>::from_edges([(0, 2)]);
+        assert!(is_isomorphic(&target, &line));
+        assert_eq!(line.neighbors(1.into()).count(), 0);
+    }
+
+    #[test]
+    fn loops_once() {
+        let line = LineGraphBuilder::new()
+            .loops(LoopPolicy::Once)
+            .build(&tadpole());
+        let target = UnGraph::<(), ()>::from_edges([(0, 1), (0, 2), (1, 2)]);
+        assert!(is_isomorphic(&target, &line));
+        assert!(is_isomorphic(&line, &crate::line_graph(&tadpole())));
+    }
+
+    #[test]
+    fn loops_twice() {
+        let line = LineGraphBuilder::new()
+            .loops(LoopPolicy::Twice)
+            .build(&tadpole());
+        let target = UnGraph::<(), ()>::from_edges([
+            (0, 1),
+            (0, 1),
+            (0, 2),
+            (1, 1),
+            (1, 2),
+            (1, 2),
+        ]);
+        assert!(is_isomorphic(&target, &line));
+
+        let simple = LineGraphBuilder::new()
+            .loops(LoopPolicy::Twice)
+            .build_simple(&tadpole());
+        let target =
+            UnGraph::<(), ()>::from_edges([(0, 1), (0, 2), (1, 1), (1, 2)]);
+        assert!(is_isomorphic(&target, &simple));
+        let e = simple.find_edge(1.into(), 1.into()).unwrap();
+        assert_eq!(simple[e].len(), 1);
+    }
+
+    #[test]
+    fn index_type() {
+        let g = UnGraph::<(), (), u8>::from_edges([(0, 1), (1, 2), (2, 0)]);
+        let line: UnGraph<(), (), usize> =
+            LineGraphBuilder::new().index_type::<usize>().build(&g);
+        assert!(is_isomorphic(&g, &line));
+        // by default, the index type of the input is kept
+        let line: UnGraph<(), (), u8> = LineGraphBuilder::new().build(&g);
+        assert!(is_isomorphic(&g, &line));
+        let line: UnGraph<(), (), u8> = crate::line_graph(&g);
+        assert!(is_isomorphic(&g, &line));
+
+        let mut star = UnGraph::<(), ()>::default();
+        let center = star.add_node(());
+        for _ in 0..400 {
+            let n = star.add_node(());
+            star.add_edge(center, n, ());
+        }
+        let builder = LineGraphBuilder::new().index_type::<u16>();
+        assert!(matches!(
+            builder.try_build(&star, usize::MAX),
+            Err(LineGraphError::IndexOverflow { .. })
+        ));
+        let builder = builder.loops(LoopPolicy::Twice);
+        let mut g = UnGraph::<(), ()>::from_edges([(0, 0), (0, 0), (0, 1)]);
+        assert_eq!(builder.try_build(&g, 10).unwrap().edge_count(), 10);
+        g.remove_edge(2.into());
+        assert!(matches!(
+            builder.try_build(&g, 5),
+            Err(LineGraphError::BudgetExceeded(_))
+        ));
+    }
+}
@@ -0,0 +1,306 @@
+use crate::{
+    build_line_graph, input::edge_references, LineGraphEdgeRef, LoopPolicy,
+};
+
+use petgraph::{
+    graph::{DefaultIx, NodeIndex, UnGraph},
+    visit::{
+        EdgeRef, GraphProp, IntoEdgeReferences, IntoNodeReferences,
+        NodeIndexable, NodeRef,
+    },
+    Undirected,
+};
+
+/// A vertex or an edge of the original graph
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Element<N, E> {
+    /// A vertex
+    Vertex(N),
+    /// An edge
+    Edge(E),
+}
+
+/// Construct the total graph for `g`
+///
+/// The nodes of the [total graph](https://en.wikipedia.org/wiki/Total_graph)
+/// are the vertices and edges of `g`. Two vertices are connected by an
+/// edge for each original edge between them, two edges are connected
+/// as in the [line graph](crate::line_graph), and each edge is
+/// connected to its endpoints.
+///
+/// The first nodes of the total graph are the vertices of `g` in the
+/// order of `g.node_references()`, followed by the edges in the order of
+/// `g.edge_references()`.
+///
+/// # Example
+///
+/// ```rust
+/// use line_graph::total_graph;
+/// use petgraph::{algo::is_isomorphic, graph::UnGraph};
+///
+/// let g = UnGraph::<(), ()>::from_edges([(0, 1)]);
+/// let triangle = UnGraph::<(), ()>::from_edges([(0, 1), (1, 2), (2, 0)]);
+/// assert!(is_isomorphic(&total_graph(&g), &triangle));
+/// ```
+pub fn total_graph<G>(
+    g: G,
+) -> UnGraph<Element<G::NodeWeight, G::EdgeWeight>, (), DefaultIx>
+where
+    G: IntoNodeReferences
+        + IntoEdgeReferences
+        + NodeIndexable
+        + GraphProp<EdgeType = Undirected>,
+    G::EdgeRef: LineGraphEdgeRef,
+    G::NodeWeight: Clone,
+    G::EdgeWeight: Clone,
+{
+    build_derived_graph(g, true, true).0
+}
+
+/// Construct the subdivision graph for `g`
+///
+/// The [subdivision
+/// graph](https://en.wikipedia.org/wiki/Homeomorphism_(graph_theory)#Subdivisions)
+/// is obtained by inserting a new vertex into each edge of `g`. The
+/// first nodes of the subdivision graph are the vertices of `g` in the
+/// order of `g.node_references()`, followed by the new vertices in the
+/// order of `g.edge_references()`. The returned mapping contains the
+/// original vertex or edge for each node.
+///
+/// # Example
+///
+/// ```rust
+/// use line_graph::{subdivision_graph, Element};
+/// use petgraph::graph::{EdgeIndex, NodeIndex, UnGraph};
+///
+/// let g = UnGraph::<(), ()>::from_edges([(0, 1), (1, 2)]);
+/// let (s, map) = subdivision_graph(&g);
+/// assert_eq!(s.node_count(), 5);
+/// assert_eq!(s.edge_count(), 4);
+/// assert_eq!(map[1], Element::Vertex(NodeIndex::new(1)));
+/// assert_eq!(map[3], Element::Edge(EdgeIndex::new(0)));
+/// ```
+#[allow(clippy::type_complexity)]
+pub fn subdivision_graph<G>(
+    g: G,
+) -> (
+    UnGraph<Element<G::NodeWeight, G::EdgeWeight>, (), DefaultIx>,
+    Vec<Element<G::NodeId, G::EdgeId>>,
+)
+where
+    G: IntoNodeReferences
+        + IntoEdgeReferences
+        + NodeIndexable
+        + GraphProp<EdgeType = Undirected>,
+    G::EdgeRef: LineGraphEdgeRef,
+    G::NodeWeight: Clone,
+    G::EdgeWeight: Clone,
+{
+    build_derived_graph(g, false, false)
+}
+
+/// Construct the middle graph for `g`
+///
+/// The nodes of the middle graph are the vertices and edges of
+/// `g`. Two edges are connected as in the [line
+/// graph](crate::line_graph), and each edge is connected to its
+/// endpoints. Equivalently, the middle graph is the line graph of the
+/// graph obtained by attaching a pendant edge to each vertex of `g`.
+///
+/// The first nodes of the middle graph are the vertices of `g` in the
+/// order of `g.node_references()`, followed by the edges in the order of
+/// `g.edge_references()`. The returned mapping contains the original
+/// vertex or edge for each node.
+///
+/// # Example
+///
+/// ```rust
+/// use line_graph::middle_graph;
+/// use petgraph::{algo::is_isomorphic, graph::UnGraph};
+///
+/// let g = UnGraph::<(), ()>::from_edges([(0, 1)]);
+/// let (m, _) = middle_graph(&g);
+/// let path = UnGraph::<(), ()>::from_edges([(0, 1), (1, 2)]);
+/// assert!(is_isomorphic(&m, &path));
+/// ```
+#[allow(clippy::type_complexity)]
+pub fn middle_graph<G>(
+    g: G,
+) -> (
+    UnGraph<Element<G::NodeWeight, G::EdgeWeight>, (), DefaultIx>,
+    Vec<Element<G::NodeId, G::EdgeId>>,
+)
+where
+    G: IntoNodeReferences
+        + IntoEdgeReferences
+        + NodeIndexable
+        + GraphProp<EdgeType = Undirected>,
+    G::EdgeRef: LineGraphEdgeRef,
+    G::NodeWeight: Clone,
+    G::EdgeWeight: Clone,
+{
+    build_derived_graph(g, false, true)
+}
+
+/// Construct a graph whose nodes are the vertices and edges of `g`
+///
+/// Each edge is always connected to its endpoints. If `adjacent_vertices`
+/// is true, the endpoints of each edge are connected as well. If
+/// `adjacent_edges` is true, the edges are connected as in the line
+/// graph.
+#[allow(clippy::type_complexity)]
+fn build_derived_graph<G>(
+    g: G,
+    adjacent_vertices: bool,
+    adjacent_edges: bool,
+) -> (
+    UnGraph<Element<G::NodeWeight, G::EdgeWeight>, (), DefaultIx>,
+    Vec<Element<G::NodeId, G::EdgeId>>,
+)
+where
+    G: IntoNodeReferences
+        + IntoEdgeReferences
+        + NodeIndexable
+        + GraphProp<EdgeType = Undirected>,
+    G::EdgeRef: LineGraphEdgeRef,
+    G::NodeWeight: Clone,
+    G::EdgeWeight: Clone,
+{
+    let line_graph: UnGraph<_, _> = build_line_graph(
+        g,
+        LoopPolicy::default(),
+        |e| e.weight().clone(),
+        |_, _, _| (),
+    );
+    let mut res = UnGraph::with_capacity(
+        g.node_bound() + line_graph.node_count(),
+        3 * line_graph.node_count() + line_graph.edge_count(),
+    );
+    let mut elements =
+        Vec::with_capacity(g.node_bound() + line_graph.node_count());
+    let mut vertices = vec![NodeIndex::end(); g.node_bound()];
+    for n in g.node_references() {
+        let idx = NodeIndexable::to_index(&g, n.id());
+        vertices[idx] = res.add_node(Element::Vertex(n.weight().clone()));
+        elements.push(Element::Vertex(n.id()));
+    }
+    let offset = res.node_count();
+    let edge_node = |n: NodeIndex| NodeIndex::new(offset + n.index());
+
+    let (nodes, edges) = line_graph.into_nodes_edges();
+    for n in nodes {
+        res.add_node(Element::Edge(n.weight));
+    }
+    // the line graph nodes follow the order of the edge references
+    for (i, e) in edge_references(g).enumerate() {
+        let s = vertices[NodeIndexable::to_index(&g, e.source())];
+        let t = vertices[NodeIndexable::to_index(&g, e.target())];
+        let n = edge_node(NodeIndex::new(i));
+        elements.push(Element::Edge(e.id()));
+        if adjacent_vertices {
+            res.add_edge(s, t, ());
+        }
+        res.add_edge(s, n, ());
+        if s != t || !adjacent_edges {
+            res.add_edge(t, n, ());
+        }
+    }
+    if adjacent_edges {
+        for e in edges {
+            res.add_edge(edge_node(e.source()), edge_node(e.target()), ());
+        }
+    }
+    (res, elements)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::line_graph;
+    use petgraph::algo::is_isomorphic;
+
+    #[test]
+    fn total_triangle() {
+        let g = UnGraph::<u32, char>::from_edges([
+            (0, 1, 'a'),
+            (1, 2, 'b'),
+            (2, 0, 'c'),
+        ]);
+        let octahedron = UnGraph::<(), ()>::from_edges([
+            (0, 2),
+            (0, 3),
+            (0, 4),
+            (0, 5),
+            (1, 2),
+            (1, 3),
+            (1, 4),
+            (1, 5),
+            (2, 4),
+            (2, 5),
+            (3, 4),
+            (3, 5),
+        ]);
+        let total = total_graph(&g);
+        assert!(is_isomorphic(&octahedron, &total));
+        let weights: Vec<_> = total.node_weights().copied().collect();
+        assert_eq!(
+            weights,
+            [
+                Element::Vertex(0),
+                Element::Vertex(0),
+                Element::Vertex(0),
+                Element::Edge('a'),
+                Element::Edge('b'),
+                Element::Edge('c'),
+            ]
+        );
+    }
+
+    #[test]
+    fn total_path() {
+        let g = UnGraph::<(), ()>::from_edges([(0, 1), (1, 2)]);
+        let target = UnGraph::<(), ()>::from_edges([
+            (0, 1),
+            (1, 2),
+            (0, 3),
+            (1, 3),
+            (1, 4),
+            (2, 4),
+            (3, 4),
+        ]);
+        assert!(is_isomorphic(&target, &total_graph(&g)));
+    }
+
+    #[test]
+    fn subdivision_and_middle() {
+        let g = UnGraph::<(), ()>::from_edges([(0, 1), (1, 2), (2, 0), (2, 3)]);
+        let (s, map) = subdivision_graph(&g);
+        assert_eq!(s.node_count(), 8);
+        assert_eq!(s.edge_count(), 8);
+        for e in g.edge_indices() {
+            let n = NodeIndex::new(4 + e.index());
+            assert_eq!(map[n.index()], Element::Edge(e));
+            let (a, b) = g.edge_endpoints(e).unwrap();
+            let mut neighbours: Vec<_> = s.neighbors(n).collect();
+            neighbours.sort();
+            assert_eq!(neighbours, [a.min(b), a.max(b)]);
+        }
+
+        let (m, map) = middle_graph(&g);
+        assert_eq!(m.node_count(), 8);
+        assert_eq!(m.edge_count(), 8 + line_graph(&g).edge_count());
+        assert_eq!(
+            map[..4],
+            g.node_indices().map(Element::Vertex).collect::<Vec<_>>()
+        );
+        for n in g.node_indices() {
+            assert_eq!(m.neighbors(n).count(), g.neighbors(n).count());
+        }
+        let mut pendant = g.clone();
+        for n in g.node_indices() {
+            let p = pendant.add_node(());
+            pendant.add_edge(n, p, ());
+        }
+        assert!(is_isomorphic(&m, &line_graph(&pendant)));
+    }
+}
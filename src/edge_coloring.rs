@@ -0,0 +1,437 @@
+//! Edge colouring via the line graph
+//!
+//! A proper edge colouring of a graph assigns colours to its edges such
+//! that adjacent edges have different colours. This is the same as a
+//! proper colouring of the nodes of the [line graph](crate::line_graph).
+//! Colours are numbered consecutively starting from zero.
+//!
+//! [misra_gries] colours the edges of a simple graph with at most `Δ +
+//! 1` colours, where `Δ` is the maximum degree. [chromatic_index]
+//! determines the minimum number of colours, but takes exponential time
+//! in the worst case. [validate] checks a colouring against the line
+//! graph.
+//!
+//! # Example
+//!
+//! ```rust
+//! use line_graph::edge_coloring::{chromatic_index, misra_gries, validate};
+//! use petgraph::graph::UnGraph;
+//!
+//! let k4 = UnGraph::<(), ()>::from_edges([
+//!     (0, 1), (0, 2), (0, 3), (1, 2), (1, 3), (2, 3)
+//! ]);
+//! let colors = misra_gries(&k4).unwrap();
+//! assert!(validate(&k4, &colors).is_ok());
+//! assert!(colors.values().all(|&c| c < 4));
+//!
+//! let (n, colors) = chromatic_index(&k4);
+//! assert_eq!(n, 3);
+//! assert!(validate(&k4, &colors).is_ok());
+//! ```
+use crate::line_graph_with;
+
+use petgraph::{
+    graph::{DefaultIx, EdgeIndex, IndexType, NodeIndex, UnGraph},
+    visit::EdgeRef,
+};
+use std::{collections::HashMap, error::Error, fmt};
+
+/// Error indicating that a graph is not simple
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum NotSimple<Ix = DefaultIx> {
+    /// The graph contains a self-loop
+    Loop(EdgeIndex<Ix>),
+    /// The graph contains parallel edges
+    ParallelEdges(EdgeIndex<Ix>, EdgeIndex<Ix>),
+}
+
+impl<Ix: IndexType> fmt::Display for NotSimple<Ix> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Loop(e) => write!(f, "edge {} is a self-loop", e.index()),
+            Self::ParallelEdges(e1, e2) => write!(
+                f,
+                "edges {} and {} are parallel",
+                e1.index(),
+                e2.index()
+            ),
+        }
+    }
+}
+
+impl<Ix: IndexType> Error for NotSimple<Ix> {}
+
+/// Error indicating that an edge colouring is not proper
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum InvalidColoring<Ix = DefaultIx> {
+    /// The edge has no colour
+    Missing(EdgeIndex<Ix>),
+    /// The adjacent edges have the same colour
+    Conflict(EdgeIndex<Ix>, EdgeIndex<Ix>),
+}
+
+impl<Ix: IndexType> fmt::Display for InvalidColoring<Ix> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Missing(e) => write!(f, "edge {} has no colour", e.index()),
+            Self::Conflict(e1, e2) => write!(
+                f,
+                "adjacent edges {} and {} have the same colour",
+                e1.index(),
+                e2.index()
+            ),
+        }
+    }
+}
+
+impl<Ix: IndexType> Error for InvalidColoring<Ix> {}
+
+/// Colour the edges of the simple graph `g` with at most `Δ + 1`
+/// colours
+///
+/// Implements the algorithm by Misra and Gries, where `Δ` is the
+/// maximum degree of `g`. Returns an error if `g` has self-loops or
+/// parallel edges.
+pub fn misra_gries<N, E, Ix: IndexType>(
+    g: &UnGraph<N, E, Ix>,
+) -> Result<HashMap<EdgeIndex<Ix>, usize>, NotSimple<Ix>> {
+    check_simple(g)?;
+    let max_degree = g.node_indices().map(|v| g.edges(v).count()).max();
+    let mut colors = Coloring {
+        graph: g,
+        colors: vec![None; g.edge_count()],
+        at: vec![vec![None; max_degree.unwrap_or(0) + 1]; g.node_count()],
+    };
+    for e in g.edge_indices() {
+        colors.color_edge(e);
+    }
+    Ok(g.edge_indices()
+        .zip(colors.colors)
+        .map(|(e, c)| (e, c.unwrap()))
+        .collect())
+}
+
+/// Determine the chromatic index of `g` together with an optimal edge
+/// colouring
+///
+/// The chromatic index is the minimum number of colours in a proper
+/// edge colouring. As in [line_graph](crate::line_graph), a self-loop
+/// is adjacent to each other edge at its vertex, but not to itself.
+///
+/// The colouring is found by a backtracking search on the line graph,
+/// which takes exponential time in the worst case. Only use this for
+/// small graphs.
+pub fn chromatic_index<N, E, Ix: IndexType>(
+    g: &UnGraph<N, E, Ix>,
+) -> (usize, HashMap<EdgeIndex<Ix>, usize>) {
+    let line = line_graph_with(g, |_| (), |_, _, _| ());
+    let mut adjacent = vec![Vec::new(); line.node_count()];
+    for e in line.edge_references() {
+        let (s, t) = (e.source().index(), e.target().index());
+        if !adjacent[s].contains(&t) {
+            adjacent[s].push(t);
+            adjacent[t].push(s);
+        }
+    }
+    // all edges at a vertex form a clique in the line graph
+    let min = g.node_indices().map(|v| g.edges(v).count()).max();
+    let mut k = min.unwrap_or(0);
+    loop {
+        if let Some(colors) = color_nodes(&adjacent, k) {
+            let colors = g.edge_indices().zip(colors).collect();
+            return (k, colors);
+        }
+        k += 1;
+    }
+}
+
+/// Check that `colors` is a proper edge colouring of `g`
+///
+/// Each edge of `g` has to be coloured and any two edges connected in
+/// the [line graph](crate::line_graph) have to have different colours.
+pub fn validate<N, E, Ix: IndexType>(
+    g: &UnGraph<N, E, Ix>,
+    colors: &HashMap<EdgeIndex<Ix>, usize>,
+) -> Result<(), InvalidColoring<Ix>> {
+    if let Some(e) = g.edge_indices().find(|e| !colors.contains_key(e)) {
+        return Err(InvalidColoring::Missing(e));
+    }
+    let line = line_graph_with(g, |e| e.id(), |_, _, _| ());
+    for e in line.edge_references() {
+        let (e1, e2) = (line[e.source()], line[e.target()]);
+        if colors[&e1] == colors[&e2] {
+            return Err(InvalidColoring::Conflict(e1, e2));
+        }
+    }
+    Ok(())
+}
+
+fn check_simple<N, E, Ix: IndexType>(
+    g: &UnGraph<N, E, Ix>,
+) -> Result<(), NotSimple<Ix>> {
+    let mut seen = HashMap::new();
+    for e in g.edge_references() {
+        let (s, t) = (e.source(), e.target());
+        if s == t {
+            return Err(NotSimple::Loop(e.id()));
+        }
+        if let Some(&other) = seen.get(&(s.min(t), s.max(t))) {
+            return Err(NotSimple::ParallelEdges(other, e.id()));
+        }
+        seen.insert((s.min(t), s.max(t)), e.id());
+    }
+    Ok(())
+}
+
+/// A partial edge colouring for the Misra-Gries algorithm
+struct Coloring<'a, N, E, Ix: IndexType> {
+    graph: &'a UnGraph<N, E, Ix>,
+    colors: Vec<Option<usize>>,
+    /// For each vertex, the incident edge with each colour
+    at: Vec<Vec<Option<EdgeIndex<Ix>>>>,
+}
+
+impl<N, E, Ix: IndexType> Coloring<'_, N, E, Ix> {
+    fn is_free(&self, v: NodeIndex<Ix>, c: usize) -> bool {
+        self.at[v.index()][c].is_none()
+    }
+
+    fn free_color(&self, v: NodeIndex<Ix>) -> usize {
+        self.at[v.index()].iter().position(Option::is_none).unwrap()
+    }
+
+    fn set(&mut self, e: EdgeIndex<Ix>, c: Option<usize>) {
+        let (s, t) = self.graph.edge_endpoints(e).unwrap();
+        if let Some(old) = self.colors[e.index()] {
+            self.at[s.index()][old] = None;
+            self.at[t.index()][old] = None;
+        }
+        if let Some(c) = c {
+            self.at[s.index()][c] = Some(e);
+            self.at[t.index()][c] = Some(e);
+        }
+        self.colors[e.index()] = c;
+    }
+
+    fn color_edge(&mut self, e: EdgeIndex<Ix>) {
+        let (u, v) = self.graph.edge_endpoints(e).unwrap();
+
+        // maximal fan of u starting with v
+        let mut fan = vec![(v, e)];
+        loop {
+            let &(last, _) = fan.last().unwrap();
+            let next = self.graph.edges(u).find(|f| {
+                let x = f.target();
+                match self.colors[f.id().index()] {
+                    Some(c) => {
+                        self.is_free(last, c)
+                            && fan.iter().all(|&(y, _)| y != x)
+                    }
+                    None => false,
+                }
+            });
+            match next {
+                Some(f) => fan.push((f.target(), f.id())),
+                None => break,
+            }
+        }
+
+        let c = self.free_color(u);
+        let d = self.free_color(fan.last().unwrap().0);
+
+        // invert the cd path starting at u
+        let mut path = Vec::new();
+        let (mut current, mut color) = (u, d);
+        while let Some(f) = self.at[current.index()][color] {
+            path.push(f);
+            let (s, t) = self.graph.edge_endpoints(f).unwrap();
+            current = if s == current { t } else { s };
+            color = if color == c { d } else { c };
+        }
+        let path_colors: Vec<_> =
+            path.iter().map(|f| self.colors[f.index()]).collect();
+        for &f in &path {
+            self.set(f, None);
+        }
+        for (f, old) in path.into_iter().zip(path_colors) {
+            let new = if old == Some(c) { d } else { c };
+            self.set(f, Some(new));
+        }
+
+        // find the end of the fan prefix on which d is free
+        let mut w = 0;
+        for (i, &(x, f)) in fan.iter().enumerate() {
+            if i > 0 {
+                match self.colors[f.index()] {
+                    Some(c) if self.is_free(fan[i - 1].0, c) => {}
+                    _ => break,
+                }
+            }
+            if self.is_free(x, d) {
+                w = i;
+                break;
+            }
+        }
+
+        // rotate the fan prefix and colour its last edge with d
+        let rotated: Vec<_> = fan[1..=w]
+            .iter()
+            .map(|&(_, f)| self.colors[f.index()])
+            .collect();
+        for &(_, f) in &fan[..=w] {
+            self.set(f, None);
+        }
+        for (&(_, f), c) in fan.iter().zip(rotated) {
+            self.set(f, c);
+        }
+        self.set(fan[w].1, Some(d));
+    }
+}
+
+/// Colour the nodes of a graph with at most `k` colours
+///
+/// Uses backtracking, always choosing the node with the largest number
+/// of distinct colours among its neighbours.
+fn color_nodes(adjacent: &[Vec<usize>], k: usize) -> Option<Vec<usize>> {
+    let mut colors = vec![None; adjacent.len()];
+    // for each node, the number of neighbours with each colour
+    let mut neighbor_colors = vec![vec![0; k]; adjacent.len()];
+    if assign(adjacent, k, &mut colors, &mut neighbor_colors) {
+        Some(colors.into_iter().map(Option::unwrap).collect())
+    } else {
+        None
+    }
+}
+
+fn assign(
+    adjacent: &[Vec<usize>],
+    k: usize,
+    colors: &mut [Option<usize>],
+    neighbor_colors: &mut [Vec<usize>],
+) -> bool {
+    let saturation =
+        |n: usize| neighbor_colors[n].iter().filter(|&&c| c > 0).count();
+    let next = (0..adjacent.len())
+        .filter(|&n| colors[n].is_none())
+        .max_by_key(|&n| (saturation(n), adjacent[n].len()));
+    let Some(n) = next else {
+        return true;
+    };
+    // colours beyond the first unused one are equivalent
+    let used = colors.iter().flatten().max().map_or(0, |&c| c + 1);
+    for c in 0..k.min(used + 1) {
+        if neighbor_colors[n][c] > 0 {
+            continue;
+        }
+        colors[n] = Some(c);
+        for &m in &adjacent[n] {
+            neighbor_colors[m][c] += 1;
+        }
+        if assign(adjacent, k, colors, neighbor_colors) {
+            return true;
+        }
+        for &m in &adjacent[n] {
+            neighbor_colors[m][c] -= 1;
+        }
+        colors[n] = None;
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::random_graph;
+
+    fn petersen() -> UnGraph<(), ()> {
+        UnGraph::from_edges([
+            (0, 1),
+            (1, 2),
+            (2, 3),
+            (3, 4),
+            (4, 0),
+            (0, 5),
+            (1, 6),
+            (2, 7),
+            (3, 8),
+            (4, 9),
+            (5, 7),
+            (7, 9),
+            (9, 6),
+            (6, 8),
+            (8, 5),
+        ])
+    }
+
+    #[test]
+    fn misra_gries_random() {
+        for seed in 0..100 {
+            let g = random_graph(20, 5 * seed as usize, seed);
+            let colors = misra_gries(&g).unwrap();
+            assert_eq!(validate(&g, &colors), Ok(()));
+            let max_degree =
+                g.node_indices().map(|v| g.edges(v).count()).max().unwrap();
+            assert!(colors.values().all(|&c| c <= max_degree));
+        }
+        let colors = misra_gries(&petersen()).unwrap();
+        assert_eq!(validate(&petersen(), &colors), Ok(()));
+    }
+
+    #[test]
+    fn not_simple() {
+        let g = UnGraph::<(), ()>::from_edges([(0, 1), (1, 2), (1, 0)]);
+        assert_eq!(
+            misra_gries(&g),
+            Err(NotSimple::ParallelEdges(0.into(), 2.into()))
+        );
+        let g = UnGraph::<(), ()>::from_edges([(0, 1), (1, 1)]);
+        assert_eq!(misra_gries(&g), Err(NotSimple::Loop(1.into())));
+    }
+
+    #[test]
+    fn exact() {
+        let cycle = |n: u32| {
+            UnGraph::<(), ()>::from_edges((0..n).map(|i| (i, (i + 1) % n)))
+        };
+        assert_eq!(chromatic_index(&cycle(6)).0, 2);
+        assert_eq!(chromatic_index(&cycle(7)).0, 3);
+        let (n, colors) = chromatic_index(&petersen());
+        assert_eq!(n, 4);
+        assert_eq!(validate(&petersen(), &colors), Ok(()));
+
+        // Shannon multigraph: triangle with doubled edges
+        let shannon = UnGraph::<(), ()>::from_edges([
+            (0, 1),
+            (0, 1),
+            (1, 2),
+            (1, 2),
+            (2, 0),
+            (2, 0),
+        ]);
+        let (n, colors) = chromatic_index(&shannon);
+        assert_eq!(n, 6);
+        assert_eq!(validate(&shannon, &colors), Ok(()));
+
+        let tadpole = UnGraph::<(), ()>::from_edges([(0, 1), (1, 1), (1, 2)]);
+        assert_eq!(chromatic_index(&tadpole).0, 3);
+        assert_eq!(chromatic_index(&UnGraph::<(), ()>::default()).0, 0);
+    }
+
+    #[test]
+    fn invalid() {
+        let g = UnGraph::<(), ()>::from_edges([(0, 1), (1, 2), (2, 3)]);
+        let mut colors: HashMap<_, _> =
+            g.edge_indices().map(|e| (e, 0)).collect();
+        assert_eq!(
+            validate(&g, &colors),
+            Err(InvalidColoring::Conflict(0.into(), 1.into()))
+        );
+        colors.insert(1.into(), 1);
+        assert_eq!(validate(&g, &colors), Ok(()));
+        colors.remove(&2.into());
+        assert_eq!(
+            validate(&g, &colors),
+            Err(InvalidColoring::Missing(2.into()))
+        );
+    }
+}
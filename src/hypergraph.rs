@@ -0,0 +1,218 @@
+use petgraph::graph::{EdgeIndex, NodeIndex, UnGraph};
+
+/// An undirected hypergraph with weighted vertices and hyperedges
+///
+/// Each hyperedge connects an arbitrary set of vertices.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Hypergraph<N, E> {
+    nodes: Vec<N>,
+    edges: Vec<(Vec<NodeIndex>, E)>,
+}
+
+impl<N, E> Default for Hypergraph<N, E> {
+    fn default() -> Self {
+        Self {
+            nodes: Vec::new(),
+            edges: Vec::new(),
+        }
+    }
+}
+
+impl<N, E> Hypergraph<N, E> {
+    /// Create an empty hypergraph
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a vertex with weight `weight`
+    pub fn add_node(&mut self, weight: N) -> NodeIndex {
+        self.nodes.push(weight);
+        NodeIndex::new(self.nodes.len() - 1)
+    }
+
+    /// Add a hyperedge connecting `vertices` with weight `weight`
+    ///
+    /// Repeated vertices are only included once.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any of the vertices does not exist.
+    pub fn add_edge<I>(&mut self, vertices: I, weight: E) -> EdgeIndex
+    where
+        I: IntoIterator<Item = NodeIndex>,
+    {
+        let mut vertices: Vec<_> = vertices.into_iter().collect();
+        vertices.sort_unstable();
+        vertices.dedup();
+        if let Some(v) = vertices.last() {
+            assert!(v.index() < self.nodes.len(), "vertex does not exist");
+        }
+        self.edges.push((vertices, weight));
+        EdgeIndex::new(self.edges.len() - 1)
+    }
+
+    /// The number of vertices
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// The number of hyperedges
+    pub fn edge_count(&self) -> usize {
+        self.edges.len()
+    }
+
+    /// The weight of vertex `n`
+    pub fn node_weight(&self, n: NodeIndex) -> Option<&N> {
+        self.nodes.get(n.index())
+    }
+
+    /// The weight of hyperedge `e`
+    pub fn edge_weight(&self, e: EdgeIndex) -> Option<&E> {
+        self.edges.get(e.index()).map(|(_, w)| w)
+    }
+
+    /// The vertices connected by hyperedge `e`, in ascending order
+    pub fn vertices(&self, e: EdgeIndex) -> Option<&[NodeIndex]> {
+        self.edges.get(e.index()).map(|(v, _)| v.as_slice())
+    }
+}
+
+/// Construct the line graph of the hypergraph `h`
+///
+/// Each node of the line graph corresponds to the hyperedge of `h` with
+/// the same index and has its weight. Two nodes are connected if the
+/// hyperedges share at least `k` vertices, where `k = 0` is treated
+/// like `k = 1`. The weight of a line graph edge is the intersection
+/// of the two hyperedges in ascending order.
+///
+/// In contrast to [line_graph](crate::line_graph), there is at most
+/// one edge between any two line graph nodes.
+///
+/// # Example
+///
+/// ```rust
+/// use line_graph::{hypergraph_line_graph, Hypergraph};
+///
+/// let mut h = Hypergraph::new();
+/// let v: Vec<_> = (0..4).map(|_| h.add_node(())).collect();
+/// h.add_edge([v[0], v[1], v[2]], "a");
+/// h.add_edge([v[1], v[2], v[3]], "b");
+/// h.add_edge([v[0], v[3]], "c");
+///
+/// let line = hypergraph_line_graph(&h, 1);
+/// assert_eq!(line.edge_count(), 3);
+/// let line = hypergraph_line_graph(&h, 2);
+/// assert_eq!(line.edge_count(), 1);
+/// assert_eq!(line.edge_weights().next().unwrap(), &[v[1], v[2]]);
+/// ```
+pub fn hypergraph_line_graph<N, E: Clone>(
+    h: &Hypergraph<N, E>,
+    k: usize,
+) -> UnGraph<E, Vec<NodeIndex>> {
+    let k = k.max(1);
+    let mut incident = vec![Vec::new(); h.node_count()];
+    for (e, (vertices, _)) in h.edges.iter().enumerate() {
+        for v in vertices {
+            incident[v.index()].push(e);
+        }
+    }
+
+    let mut res = UnGraph::with_capacity(h.edge_count(), 0);
+    for (_, w) in &h.edges {
+        res.add_node(w.clone());
+    }
+    let mut shared = vec![Vec::new(); h.edge_count()];
+    let mut neighbours = Vec::new();
+    for (e, (vertices, _)) in h.edges.iter().enumerate() {
+        for &v in vertices {
+            for &f in incident[v.index()].iter().filter(|&&f| f > e) {
+                if shared[f].is_empty() {
+                    neighbours.push(f);
+                }
+                shared[f].push(v);
+            }
+        }
+        neighbours.sort_unstable();
+        for f in neighbours.drain(..) {
+            let intersection = std::mem::take(&mut shared[f]);
+            if intersection.len() >= k {
+                res.add_edge(
+                    NodeIndex::new(e),
+                    NodeIndex::new(f),
+                    intersection,
+                );
+            }
+        }
+    }
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simple_line_graph_with;
+    use petgraph::visit::{EdgeRef, NodeRef};
+
+    #[test]
+    fn graph() {
+        let g = UnGraph::<(), ()>::from_edges([
+            (0, 1),
+            (0, 1),
+            (1, 2),
+            (2, 0),
+            (2, 3),
+            (3, 3),
+        ]);
+        let mut h = Hypergraph::new();
+        for _ in g.node_indices() {
+            h.add_node(());
+        }
+        for e in g.edge_references() {
+            h.add_edge([e.source(), e.target()], ());
+        }
+        let line = hypergraph_line_graph(&h, 1);
+        let simple = simple_line_graph_with(
+            &g,
+            |_| (),
+            |_, _, v| v.iter().map(|n| n.id()).collect(),
+        );
+        let edges = |g: &UnGraph<(), Vec<NodeIndex>>| {
+            let mut edges: Vec<_> = g
+                .edge_references()
+                .map(|e| {
+                    let (s, t) = (e.source(), e.target());
+                    let mut w = e.weight().clone();
+                    w.sort();
+                    (s.min(t), s.max(t), w)
+                })
+                .collect();
+            edges.sort();
+            edges
+        };
+        assert_eq!(edges(&line), edges(&simple));
+
+        let line = hypergraph_line_graph(&h, 2);
+        assert_eq!(line.edge_count(), 1);
+        let e = line.edge_references().next().unwrap();
+        assert_eq!((e.source().index(), e.target().index()), (0, 1));
+    }
+
+    #[test]
+    fn threshold() {
+        let mut h = Hypergraph::new();
+        let v: Vec<_> = (0..6).map(|i| h.add_node(i)).collect();
+        h.add_edge([v[0], v[1], v[2], v[3]], 'a');
+        h.add_edge([v[3], v[2], v[1], v[1]], 'b');
+        h.add_edge([v[3], v[4], v[5]], 'c');
+        h.add_edge([], 'd');
+        assert_eq!(h.vertices(EdgeIndex::new(1)).unwrap(), &v[1..4]);
+
+        let sizes: Vec<_> = (0..5)
+            .map(|k| hypergraph_line_graph(&h, k).edge_count())
+            .collect();
+        assert_eq!(sizes, [3, 3, 1, 1, 0]);
+        let line = hypergraph_line_graph(&h, 3);
+        assert_eq!(line.node_weights().copied().collect::<String>(), "abcd");
+        assert_eq!(line.edge_weights().next().unwrap(), &v[1..4]);
+    }
+}
@@ -0,0 +1,99 @@
+use petgraph::{
+    csr,
+    graph::{self, DefaultIx, IndexType},
+    stable_graph,
+    visit::{EdgeRef, IntoEdgeReferences, ReversedEdgeReference},
+    EdgeType,
+};
+
+/// Edge reference of a graph accepted by [line_graph](crate::line_graph)
+///
+/// The generic line graph constructors require their input's edge
+/// references to implement this trait. It is implemented for the edge
+/// references of all undirected petgraph graphs, including filtered
+/// and reversed views.
+pub trait LineGraphEdgeRef: EdgeRef {
+    /// Index type of the line graph
+    ///
+    /// This is the index type of the original graph. Since the edge
+    /// references of a [GraphMap](petgraph::graphmap::GraphMap) and a
+    /// [MatrixGraph](petgraph::matrix_graph::MatrixGraph) do not
+    /// determine an index type, their line graphs use [DefaultIx].
+    type Ix: IndexType;
+
+    /// Whether this reference stands for its edge in the line graph
+    ///
+    /// An undirected [Csr](petgraph::csr::Csr) lists each edge once in
+    /// each direction, and only the direction from the smaller to the
+    /// larger node index is used. All other references are used.
+    fn is_representative(&self) -> bool {
+        true
+    }
+}
+
+impl<E, Ix: IndexType> LineGraphEdgeRef for graph::EdgeReference<'_, E, Ix> {
+    type Ix = Ix;
+}
+
+impl<E, Ix: IndexType> LineGraphEdgeRef
+    for stable_graph::EdgeReference<'_, E, Ix>
+{
+    type Ix = Ix;
+}
+
+impl<N: Copy, E> LineGraphEdgeRef for (N, N, &E) {
+    type Ix = DefaultIx;
+}
+
+impl<E, Ty: EdgeType, Ix: IndexType> LineGraphEdgeRef
+    for csr::EdgeReference<'_, E, Ty, Ix>
+{
+    type Ix = Ix;
+
+    fn is_representative(&self) -> bool {
+        Ty::is_directed() || self.source() <= self.target()
+    }
+}
+
+impl<R: LineGraphEdgeRef> LineGraphEdgeRef for ReversedEdgeReference<R> {
+    type Ix = R::Ix;
+
+    fn is_representative(&self) -> bool {
+        self.as_unreversed().is_representative()
+    }
+}
+
+/// Marker for the index type of the original graph
+///
+/// A [LineGraphBuilder](crate::LineGraphBuilder) with this index type
+/// constructs line graphs with the index type [LineGraphEdgeRef::Ix]
+/// of the original graph. This is the default.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct InputIx;
+
+/// Index type of a line graph
+///
+/// Maps the index type parameter of a
+/// [LineGraphBuilder](crate::LineGraphBuilder) to the index type of the
+/// line graph of a graph with edge references `E`.
+pub trait LineGraphIndex<E: LineGraphEdgeRef> {
+    /// The index type of the line graph
+    type Ix: IndexType;
+}
+
+impl<E: LineGraphEdgeRef> LineGraphIndex<E> for InputIx {
+    type Ix = E::Ix;
+}
+
+impl<E: LineGraphEdgeRef, Ix: IndexType> LineGraphIndex<E> for Ix {
+    type Ix = Ix;
+}
+
+/// The edge references of `g` that stand for their edges
+pub(crate) fn edge_references<G>(g: G) -> impl Iterator<Item = G::EdgeRef>
+where
+    G: IntoEdgeReferences,
+    G::EdgeRef: LineGraphEdgeRef,
+{
+    g.edge_references().filter(|e| e.is_representative())
+}
@@ -0,0 +1,222 @@
+use crate::{
+    line_graph_with,
+    size::{choose_2, incidences, line_graph_size},
+    LineGraphBuilder, LoopPolicy,
+};
+
+use petgraph::{
+    graph::{IndexType, UnGraph},
+    visit::EdgeRef,
+};
+use std::{error::Error, fmt};
+
+/// Number of nodes and edges of a graph
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct GraphSize {
+    /// Number of nodes
+    pub nodes: usize,
+    /// Number of edges
+    pub edges: usize,
+}
+
+/// Error indicating that an iterated line graph would exceed the
+/// edge budget
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct BudgetExceeded {
+    /// The number of line graph iterations
+    pub iteration: usize,
+    /// The number of edges after `iteration` iterations, saturated at
+    /// `usize::MAX`
+    pub edges: usize,
+    /// The maximum allowed number of edges
+    pub budget: usize,
+}
+
+impl fmt::Display for BudgetExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line graph iteration {} has {} edges, exceeding the budget of {}",
+            self.iteration, self.edges, self.budget
+        )
+    }
+}
+
+impl Error for BudgetExceeded {}
+
+/// Predict the sizes of the iterated line graphs `L(g)`, `L(L(g))`, ...
+///
+/// Returns the number of nodes and edges for each of the first `k`
+/// iterations. Each iteration is only considered if all previous ones
+/// have at most `budget` edges, otherwise an error is returned.
+///
+/// The size of `L(L(g))` is determined from the degrees of `g`. For
+/// deeper iterations, the intermediate line graphs up to two levels
+/// below the predicted one are constructed, but without weights.
+///
+/// # Example
+///
+/// ```rust
+/// use line_graph::{iterated_line_graph_sizes, GraphSize};
+/// use petgraph::graph::UnGraph;
+///
+/// // the star with four edges
+/// let g = UnGraph::<(), ()>::from_edges([(0, 1), (0, 2), (0, 3), (0, 4)]);
+/// let sizes = iterated_line_graph_sizes(&g, 2, 100).unwrap();
+/// assert_eq!(sizes[0], GraphSize { nodes: 4, edges: 6 });
+/// assert_eq!(sizes[1], GraphSize { nodes: 6, edges: 12 });
+/// assert!(iterated_line_graph_sizes(&g, 4, 100).is_err());
+/// ```
+pub fn iterated_line_graph_sizes<N, E, Ix: IndexType>(
+    g: &UnGraph<N, E, Ix>,
+    k: usize,
+    budget: usize,
+) -> Result<Vec<GraphSize>, BudgetExceeded> {
+    let mut sizes = Vec::with_capacity(k);
+    let push = |sizes: &mut Vec<GraphSize>, size: GraphSize| {
+        if size.edges > budget {
+            return Err(BudgetExceeded {
+                iteration: sizes.len() + 1,
+                edges: size.edges,
+                budget,
+            });
+        }
+        sizes.push(size);
+        Ok(())
+    };
+    if k == 0 {
+        return Ok(sizes);
+    }
+    push(&mut sizes, line_graph_size(g, LoopPolicy::Once))?;
+    if k == 1 {
+        return Ok(sizes);
+    }
+    push(&mut sizes, second_line_graph_size(g))?;
+    let mut current = line_graph_with(g, |_| (), |_, _, _| ());
+    while sizes.len() < k {
+        push(&mut sizes, second_line_graph_size(&current))?;
+        if sizes.len() < k {
+            current = line_graph_with(&current, |_| (), |_, _, _| ());
+        }
+    }
+    Ok(sizes)
+}
+
+/// Construct the `k`th iterated line graph `L(L(...L(g)))`
+///
+/// Since each iteration swaps node and edge weights, they have to be
+/// of the same type. Before each iteration, its size is predicted and
+/// compared to `budget`, see [iterated_line_graph_sizes]. If it has
+/// more than `budget` edges, an error is returned. The result has the
+/// same index type as `g`.
+///
+/// # Example
+///
+/// ```rust
+/// use line_graph::iterated_line_graph;
+/// use petgraph::{algo::is_isomorphic, graph::UnGraph};
+///
+/// let claw = UnGraph::<(), ()>::from_edges([(0, 1), (0, 2), (0, 3)]);
+/// let triangle = UnGraph::<(), ()>::from_edges([(0, 1), (1, 2), (2, 0)]);
+/// let l3 = iterated_line_graph(&claw, 3, 100).unwrap();
+/// assert!(is_isomorphic(&triangle, &l3));
+/// ```
+pub fn iterated_line_graph<W: Clone, Ix: IndexType>(
+    g: &UnGraph<W, W, Ix>,
+    k: usize,
+    budget: usize,
+) -> Result<UnGraph<W, W, Ix>, BudgetExceeded> {
+    let builder = LineGraphBuilder::new().index_type::<Ix>();
+    let mut current = g.clone();
+    for iteration in 1..=k {
+        let edges = line_graph_size(&current, LoopPolicy::Once).edges;
+        if edges > budget {
+            return Err(BudgetExceeded {
+                iteration,
+                edges,
+                budget,
+            });
+        }
+        current = builder.build(&current);
+    }
+    Ok(current)
+}
+
+/// Size of the line graph of the line graph of `g`
+fn second_line_graph_size<N, E, Ix: IndexType>(
+    g: &UnGraph<N, E, Ix>,
+) -> GraphSize {
+    let incidences = incidences(g, LoopPolicy::Once);
+    let edges = g
+        .edge_references()
+        .try_fold(0usize, |acc, e| {
+            let (s, t) = (e.source().index(), e.target().index());
+            let degree = if s == t {
+                incidences[s] - 1
+            } else {
+                incidences[s] + incidences[t] - 2
+            };
+            acc.checked_add(choose_2(degree)?)
+        })
+        .unwrap_or(usize::MAX);
+    GraphSize {
+        nodes: line_graph_size(g, LoopPolicy::Once).edges,
+        edges,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::line_graph;
+    use petgraph::algo::is_isomorphic;
+
+    #[test]
+    fn predicted_sizes() {
+        let graphs = [
+            UnGraph::<(), ()>::from_edges([(0, 1), (0, 2), (0, 3), (1, 4)]),
+            UnGraph::from_edges([(0, 1), (0, 1), (1, 2), (2, 2), (2, 3)]),
+            UnGraph::from_edges([
+                (0, 1),
+                (0, 2),
+                (0, 3),
+                (1, 4),
+                (2, 3),
+                (3, 4),
+            ]),
+        ];
+        for g in graphs {
+            let sizes = iterated_line_graph_sizes(&g, 4, usize::MAX).unwrap();
+            let mut current = g;
+            for size in sizes {
+                current = line_graph(&current);
+                let actual = GraphSize {
+                    nodes: current.node_count(),
+                    edges: current.edge_count(),
+                };
+                assert_eq!(size, actual);
+            }
+        }
+    }
+
+    #[test]
+    fn budget() {
+        let g = UnGraph::<(), ()>::from_edges([(0, 1), (0, 2), (0, 3), (1, 4)]);
+        let sizes = iterated_line_graph_sizes(&g, 3, usize::MAX).unwrap();
+        let err = iterated_line_graph_sizes(&g, 3, sizes[1].edges).unwrap_err();
+        assert_eq!(err.iteration, 3);
+        assert_eq!(err.edges, sizes[2].edges);
+        let err = iterated_line_graph(&g, 3, sizes[1].edges).unwrap_err();
+        assert_eq!(err.iteration, 3);
+        assert!(iterated_line_graph(&g, 3, sizes[2].edges).is_ok());
+    }
+
+    #[test]
+    fn cycle() {
+        let g = UnGraph::<(), ()>::from_edges([(0, 1), (1, 2), (2, 3), (3, 0)]);
+        for k in 0..4 {
+            let l = iterated_line_graph(&g, k, 100).unwrap();
+            assert!(is_isomorphic(&g, &l));
+        }
+    }
+}
@@ -1,11 +1,65 @@
 //! Construct the line graph of an undirected graph
 //!
-//! This crate provides a single function that takes an undirected
+//! This crate provides a function that takes an undirected
 //! [petgraph](https://github.com/petgraph/petgraph) graph and
 //! constructs the corresponding
 //! [line graph](https://en.wikipedia.org/wiki/Line_graph).
 //! Node weights are turned into edge weights and vice versa.
 //!
+//! For directed graphs, [line_digraph] constructs the directed line
+//! graph, in which there is an edge from `e1` to `e2` whenever the
+//! target of `e1` is the source of `e2`. For routing with turn costs
+//! and restrictions, [edge_expanded_graph] computes the weight of each
+//! transition or omits it.
+//!
+//! [line_graph_with_map] also returns the correspondence between
+//! the line graph and the original graph, and [stable_line_graph]
+//! accepts a `StableGraph` with removed nodes or edges.
+//!
+//! To compute the line graph weights from the original edges and
+//! vertices instead of swapping them, use [line_graph_with].
+//! [random_walk_line_graph] and [weighted_random_walk_line_graph]
+//! normalise the line graph weights such that random walks on the
+//! line graph are consistent with random walks on the original graph.
+//!
+//! Conversely, [root_graph] reconstructs a graph from its line graph,
+//! and [is_line_graph] explains why a graph is not a line graph.
+//! [lift_isomorphism] recovers the vertex map that induces an
+//! isomorphism between line graphs.
+//!
+//! Iterated line graphs are constructed by [iterated_line_graph], and
+//! [iterated_line_graph_sizes] predicts their sizes. [try_line_graph]
+//! checks the size of the line graph before constructing it.
+//! [line_graph_edge_count] and [line_graph_degree] determine the
+//! number of edges and node degrees without any construction.
+//!
+//! The related total graph, subdivision graph, and middle graph are
+//! constructed by [total_graph], [subdivision_graph], and [middle_graph].
+//!
+//! To run graph algorithms on a line graph without constructing it,
+//! use [LineGraphView].
+//!
+//! Line graphs of hypergraphs, where hyperedges sharing at least a
+//! given number of vertices are connected, are constructed by
+//! [hypergraph_line_graph].
+//!
+//! The incidence matrix `B` of a graph and the adjacency matrix
+//! `BᵀB - 2I` of its line graph are available in sparse form via
+//! [incidence_matrix] and [line_graph_adjacency_matrix].
+//!
+//! The non-backtracking [oriented_line_graph] connects consecutive
+//! half-edges that do not reverse each other. Its adjacency matrix is
+//! the [hashimoto_matrix].
+//!
+//! With the `rayon` feature enabled, `par_line_graph` constructs the
+//! line graph in parallel.
+//!
+//! The [edge_coloring] module colours the edges of a graph, which is
+//! the same as colouring the nodes of its line graph.
+//!
+//! The [link_communities] module finds overlapping communities by
+//! clustering the edges of a graph based on a weighted line graph.
+//!
 //! # Example
 //!
 //! The triangle graph is the same as its line graph.
@@ -26,54 +80,540 @@
 //!
 //! If edges are connected by two vertices, the corresponding vertices
 //! in the line graph will also be connected by two edges.
+//! Use [simple_line_graph] to obtain a line graph without parallel edges.
+//!
+//! By default, a self-loop is considered incident to its vertex once
+//! and is therefore adjacent to the other edges at that vertex, but
+//! not to itself. Use [LineGraphBuilder] with a different [LoopPolicy] to
+//! change this.
+//!
+//! Line graphs have the same index type as the original graph. Only the
+//! edge references of a [GraphMap](petgraph::graphmap::GraphMap) or a
+//! [MatrixGraph](petgraph::matrix_graph::MatrixGraph) do not determine
+//! an index type, so their line graphs use
+//! [DefaultIx](petgraph::graph::DefaultIx). Use
+//! [LineGraphBuilder::index_type] to choose a different index type. The
+//! [total_graph], [subdivision_graph], [middle_graph], [root_graph], and
+//! [hypergraph_line_graph] always use `DefaultIx`. Constructing a graph
+//! with more nodes or edges than its index type can represent panics.
 //!
+mod builder;
+mod derived;
+pub mod edge_coloring;
+mod hypergraph;
+mod input;
+mod iterated;
+pub mod link_communities;
+mod map;
+mod matrix;
+mod oriented;
+#[cfg(feature = "rayon")]
+mod parallel;
+mod random_walk;
+mod recognition;
+mod root;
+mod routing;
+mod size;
+#[cfg(test)]
+mod test_util;
+mod view;
+mod whitney;
+
+pub use builder::{LineGraphBuilder, LoopPolicy};
+pub use derived::{middle_graph, subdivision_graph, total_graph, Element};
+pub use hypergraph::{hypergraph_line_graph, Hypergraph};
+pub use input::{InputIx, LineGraphEdgeRef, LineGraphIndex};
+pub use iterated::{
+    iterated_line_graph, iterated_line_graph_sizes, BudgetExceeded, GraphSize,
+};
+pub use map::LineGraphMap;
+pub use matrix::{
+    hashimoto_matrix, incidence_matrix, line_graph_adjacency_matrix, CsrMatrix,
+};
+pub use oriented::oriented_line_graph;
+#[cfg(feature = "rayon")]
+pub use parallel::par_line_graph;
+pub use random_walk::{
+    random_walk_line_graph, weighted_random_walk_line_graph,
+};
+pub use recognition::{is_line_graph, BeinekeGraph, NotLineGraph};
+pub use root::root_graph;
+pub use routing::{
+    edge_expanded_graph, expanded_path_edges, expanded_path_vertices,
+};
+pub use size::{
+    line_graph_degree, line_graph_edge_count, try_line_graph, LineGraphError,
+};
+pub use view::{
+    LineEdgeReference, LineEdgeReferences, LineEdges, LineGraphView,
+    LineNeighbors, LineNodeReferences, LineVisitMap,
+};
+pub use whitney::{lift_isomorphism, LiftError};
+
+use input::edge_references;
 use petgraph::{
-    graph::{DefaultIx, IndexType, UnGraph},
-    visit::{EdgeRef, IntoNodeReferences},
+    graph::{DiGraph, EdgeIndex, IndexType, NodeIndex, UnGraph},
+    stable_graph::{StableGraph, StableUnGraph},
+    visit::{
+        EdgeIndexable, EdgeRef, GraphProp, IntoEdgeReferences,
+        IntoNodeReferences, NodeIndexable, NodeRef,
+    },
+    Undirected,
 };
-use std::default::Default;
+use size::{check_size, line_graph_size};
+use std::collections::HashMap;
 
 /// Construct the line graph for `g`
-pub fn line_graph<N, E, Ix>(g: &UnGraph<N, E, Ix>) -> UnGraph<E, N, DefaultIx>
+///
+/// `g` can be any undirected graph implementing the required
+/// [petgraph::visit] traits, for example a reference to a
+/// [Graph](petgraph::graph::Graph), a
+/// [StableGraph], a
+/// [GraphMap](petgraph::graphmap::GraphMap), or a filtered or
+/// reversed view of one of these.
+///
+/// An undirected [Csr](petgraph::csr::Csr) reports each edge once in
+/// each direction. Only one of the two directions becomes a line graph
+/// node, see [LineGraphEdgeRef].
+///
+/// The nodes of the line graph follow the order of
+/// `g.edge_references()`. For a `Graph`, each edge `e` of `g` becomes
+/// the node with index `e.index()` in the line graph. Use
+/// [line_graph_with_map] to also obtain the vertices shared by
+/// adjacent edges.
+pub fn line_graph<G>(
+    g: G,
+) -> UnGraph<G::EdgeWeight, G::NodeWeight, <G::EdgeRef as LineGraphEdgeRef>::Ix>
 where
-    N: Clone,
-    E: Clone + Default,
+    G: IntoNodeReferences
+        + IntoEdgeReferences
+        + NodeIndexable
+        + GraphProp<EdgeType = Undirected>,
+    G::EdgeRef: LineGraphEdgeRef,
+    G::NodeWeight: Clone,
+    G::EdgeWeight: Clone,
+{
+    build_line_graph(
+        g,
+        LoopPolicy::default(),
+        |e| e.weight().clone(),
+        |_, _, v| v.weight().clone(),
+    )
+}
+
+/// Construct the line graph for `g` together with the correspondence
+/// between the line graph and `g`
+///
+/// In contrast to [line_graph], `g` has to implement [EdgeIndexable].
+/// In particular, this excludes [Csr](petgraph::csr::Csr).
+///
+/// # Example
+///
+/// ```rust
+/// use line_graph::line_graph_with_map;
+/// use petgraph::graph::{EdgeIndex, NodeIndex, UnGraph};
+///
+/// let g = UnGraph::<(), ()>::from_edges([(0, 1), (1, 2)]);
+/// let (g_line, map) = line_graph_with_map(&g);
+/// let e = g_line.edge_indices().next().unwrap();
+/// assert_eq!(map.vertex(e), Some(NodeIndex::new(1)));
+/// let n = map.line_node(EdgeIndex::new(1)).unwrap();
+/// assert_eq!(map.endpoints(n), Some((NodeIndex::new(1), NodeIndex::new(2))));
+/// ```
+#[allow(clippy::type_complexity)]
+pub fn line_graph_with_map<G>(
+    g: G,
+) -> (
+    UnGraph<G::EdgeWeight, G::NodeWeight, <G::EdgeRef as LineGraphEdgeRef>::Ix>,
+    LineGraphMap<G::NodeId, G::EdgeId, <G::EdgeRef as LineGraphEdgeRef>::Ix>,
+)
+where
+    G: IntoNodeReferences
+        + IntoEdgeReferences
+        + NodeIndexable
+        + EdgeIndexable
+        + GraphProp<EdgeType = Undirected>,
+    G::EdgeRef: LineGraphEdgeRef,
+    G::NodeWeight: Clone,
+    G::EdgeWeight: Clone,
+{
+    build_line_graph_with_map(
+        g,
+        LoopPolicy::default(),
+        |e| e.weight().clone(),
+        |_, _, v| v.weight().clone(),
+    )
+}
+
+/// Construct the line graph for `g` with custom weights
+///
+/// The weight of each line graph node is `node_fn(e)`, where `e` is
+/// the reference to the corresponding original edge. The weight of
+/// each line graph edge is `edge_fn(e1, e2, v)`, where `e1` and `e2`
+/// are the original edges and `v` is the reference to the original
+/// vertex they share.
+///
+/// # Example
+///
+/// Join the lengths of adjacent road segments and record the junction
+/// at which they meet.
+///
+/// ```rust
+/// use line_graph::line_graph_with;
+/// use petgraph::{graph::UnGraph, visit::EdgeRef};
+///
+/// let g = UnGraph::<&str, f64>::from_edges([(0, 1, 1.), (1, 2, 2.)]);
+/// let g_line = line_graph_with(
+///     &g,
+///     |e| e.id(),
+///     |e1, e2, (v, _)| (e1.weight() + e2.weight(), v),
+/// );
+/// let e = g_line.edge_indices().next().unwrap();
+/// assert_eq!(g_line[e].0, 3.);
+/// assert_eq!(g_line[e].1.index(), 1);
+/// ```
+pub fn line_graph_with<G, NW, EW, NF, EF>(
+    g: G,
+    node_fn: NF,
+    edge_fn: EF,
+) -> UnGraph<NW, EW, <G::EdgeRef as LineGraphEdgeRef>::Ix>
+where
+    G: IntoNodeReferences
+        + IntoEdgeReferences
+        + NodeIndexable
+        + GraphProp<EdgeType = Undirected>,
+    G::EdgeRef: LineGraphEdgeRef,
+    NF: FnMut(G::EdgeRef) -> NW,
+    EF: FnMut(G::EdgeRef, G::EdgeRef, G::NodeRef) -> EW,
+{
+    build_line_graph(g, LoopPolicy::default(), node_fn, edge_fn)
+}
+
+/// Construct the simple line graph for `g`
+///
+/// In contrast to [line_graph], two line graph nodes are connected by
+/// at most one edge, even if the original edges share two
+/// vertices. The weight of each line graph edge contains the weights
+/// of all shared vertices.
+///
+/// # Example
+///
+/// ```rust
+/// use line_graph::simple_line_graph;
+/// use petgraph::graph::UnGraph;
+///
+/// let g = UnGraph::<u32, ()>::from_edges([(0, 1), (0, 1), (1, 2)]);
+/// let g_line = simple_line_graph(&g);
+/// assert_eq!(g_line.edge_count(), 3);
+/// let e = g_line.find_edge(0.into(), 1.into()).unwrap();
+/// assert_eq!(g_line[e].len(), 2);
+/// ```
+pub fn simple_line_graph<G>(
+    g: G,
+) -> UnGraph<
+    G::EdgeWeight,
+    Vec<G::NodeWeight>,
+    <G::EdgeRef as LineGraphEdgeRef>::Ix,
+>
+where
+    G: IntoNodeReferences
+        + IntoEdgeReferences
+        + NodeIndexable
+        + GraphProp<EdgeType = Undirected>,
+    G::EdgeRef: LineGraphEdgeRef,
+    G::NodeWeight: Clone,
+    G::EdgeWeight: Clone,
+{
+    simple_line_graph_with(
+        g,
+        |e| e.weight().clone(),
+        |_, _, shared| shared.iter().map(|v| v.weight().clone()).collect(),
+    )
+}
+
+/// Construct the simple line graph for `g` with custom weights
+///
+/// This is the same as [line_graph_with], except that two line graph
+/// nodes are connected by at most one edge. The weight of each line
+/// graph edge is `edge_fn(e1, e2, shared)`, where `shared` contains
+/// the references to all original vertices shared by `e1` and `e2`.
+///
+/// # Example
+///
+/// Count the number of shared vertices.
+///
+/// ```rust
+/// use line_graph::simple_line_graph_with;
+/// use petgraph::graph::UnGraph;
+///
+/// let g = UnGraph::<(), ()>::from_edges([(0, 1), (0, 1), (1, 2)]);
+/// let g_line = simple_line_graph_with(&g, |_| (), |_, _, v| v.len());
+/// let mut counts: Vec<_> = g_line.edge_weights().copied().collect();
+/// counts.sort();
+/// assert_eq!(counts, [1, 1, 2]);
+/// ```
+pub fn simple_line_graph_with<G, NW, EW, NF, EF>(
+    g: G,
+    node_fn: NF,
+    edge_fn: EF,
+) -> UnGraph<NW, EW, <G::EdgeRef as LineGraphEdgeRef>::Ix>
+where
+    G: IntoNodeReferences
+        + IntoEdgeReferences
+        + NodeIndexable
+        + GraphProp<EdgeType = Undirected>,
+    G::EdgeRef: LineGraphEdgeRef,
+    NF: FnMut(G::EdgeRef) -> NW,
+    EF: FnMut(G::EdgeRef, G::EdgeRef, &[G::NodeRef]) -> EW,
+{
+    build_simple_line_graph(g, LoopPolicy::default(), node_fn, edge_fn)
+}
+
+pub(crate) fn build_simple_line_graph<G, NW, EW, NF, EF, Ix>(
+    g: G,
+    loops: LoopPolicy,
+    mut node_fn: NF,
+    mut edge_fn: EF,
+) -> UnGraph<NW, EW, Ix>
+where
+    G: IntoNodeReferences
+        + IntoEdgeReferences
+        + NodeIndexable
+        + GraphProp<EdgeType = Undirected>,
+    G::EdgeRef: LineGraphEdgeRef,
+    NF: FnMut(G::EdgeRef) -> NW,
+    EF: FnMut(G::EdgeRef, G::EdgeRef, &[G::NodeRef]) -> EW,
+    Ix: IndexType,
+{
+    let multi: UnGraph<_, _, Ix> =
+        build_line_graph(g, loops, |e| e, |_, _, v| v);
+    let mut pos = HashMap::new();
+    let mut adjacent: Vec<(_, _, Vec<_>)> = Vec::new();
+    for e in multi.edge_references() {
+        let key = (e.source(), e.target());
+        let idx = *pos.entry(key).or_insert_with(|| {
+            adjacent.push((e.source(), e.target(), Vec::new()));
+            adjacent.len() - 1
+        });
+        adjacent[idx].2.push(*e.weight());
+    }
+
+    let mut line_graph =
+        UnGraph::with_capacity(multi.node_count(), adjacent.len());
+    for &e in multi.node_weights() {
+        line_graph.add_node(node_fn(e));
+    }
+    for (v1, v2, shared) in adjacent {
+        let weight = edge_fn(multi[v1], multi[v2], &shared);
+        line_graph.add_edge(v1, v2, weight);
+    }
+    line_graph
+}
+
+pub(crate) fn build_line_graph<G, NW, EW, NF, EF, Ix>(
+    g: G,
+    loops: LoopPolicy,
+    mut node_fn: NF,
+    edge_fn: EF,
+) -> UnGraph<NW, EW, Ix>
+where
+    G: IntoNodeReferences
+        + IntoEdgeReferences
+        + NodeIndexable
+        + GraphProp<EdgeType = Undirected>,
+    G::EdgeRef: LineGraphEdgeRef,
+    NF: FnMut(G::EdgeRef) -> NW,
+    EF: FnMut(G::EdgeRef, G::EdgeRef, G::NodeRef) -> EW,
     Ix: IndexType,
 {
-    let mut line_graph = UnGraph::with_capacity(g.edge_count(), 0);
-    for _ in 0..g.edge_count() {
-        line_graph.add_node(Default::default());
+    let size = line_graph_size(g, loops);
+    let mut line_graph = allocate_line_graph(size);
+    let (edges, incident) =
+        add_line_nodes(g, loops, &mut line_graph, |_| {}, &mut node_fn);
+    add_line_edges(g, &mut line_graph, &edges, &incident, edge_fn, |_| {});
+    line_graph
+}
+
+// Empty graph with capacity for a line graph of the given size
+//
+// Panics if the line graph cannot be indexed with `Ix`. We check this
+// before allocating, since the edge count is potentially huge.
+pub(crate) fn allocate_line_graph<N, E, Ix: IndexType>(
+    size: GraphSize,
+) -> UnGraph<N, E, Ix> {
+    if let Err(err) = check_size::<Ix>(size, usize::MAX) {
+        panic!("{err}");
     }
-    for (nidx, nwt) in g.node_references() {
-        for (s, e1) in g.edges(nidx).enumerate() {
-            for e2 in g.edges(nidx).skip(s + 1) {
-                let (v1, v2) = {
-                    use petgraph::visit::EdgeIndexable;
-                    (g.to_index(e1.id()), g.to_index(e2.id()))
-                };
-                let (v1, v2) = {
-                    use petgraph::visit::NodeIndexable;
-                    (line_graph.from_index(v1), line_graph.from_index(v2))
-                };
-                line_graph.add_edge(v1, v2, nwt.clone());
+    UnGraph::with_capacity(size.nodes, size.edges)
+}
+
+#[allow(clippy::type_complexity)]
+pub(crate) fn build_line_graph_with_map<G, NW, EW, NF, EF, Ix>(
+    g: G,
+    loops: LoopPolicy,
+    mut node_fn: NF,
+    edge_fn: EF,
+) -> (UnGraph<NW, EW, Ix>, LineGraphMap<G::NodeId, G::EdgeId, Ix>)
+where
+    G: IntoNodeReferences
+        + IntoEdgeReferences
+        + NodeIndexable
+        + EdgeIndexable
+        + GraphProp<EdgeType = Undirected>,
+    G::EdgeRef: LineGraphEdgeRef,
+    NF: FnMut(G::EdgeRef) -> NW,
+    EF: FnMut(G::EdgeRef, G::EdgeRef, G::NodeRef) -> EW,
+    Ix: IndexType,
+{
+    let size = line_graph_size(g, loops);
+    let mut line_graph = allocate_line_graph(size);
+    let mut map = LineGraphMap::with_capacity(size.nodes, size.edges);
+    let add_node = |e: G::EdgeRef| {
+        let idx = EdgeIndexable::to_index(&g, e.id());
+        map.add_node(e.id(), idx, e.source(), e.target());
+    };
+    let (edges, incident) =
+        add_line_nodes(g, loops, &mut line_graph, add_node, &mut node_fn);
+    add_line_edges(g, &mut line_graph, &edges, &incident, edge_fn, |v| {
+        map.add_edge(v)
+    });
+    (line_graph, map)
+}
+
+/// Add a line graph node for each edge of `g`
+///
+/// `on_node` is called with each edge after its node has been added.
+/// Returns the edges of `g` in the order of the line graph nodes and,
+/// for each vertex of `g`, the line graph nodes of the incident edges
+/// according to `loops`.
+#[allow(clippy::type_complexity)]
+pub(crate) fn add_line_nodes<G, NW, EW, NF, Ix>(
+    g: G,
+    loops: LoopPolicy,
+    line_graph: &mut UnGraph<NW, EW, Ix>,
+    mut on_node: impl FnMut(G::EdgeRef),
+    mut node_fn: NF,
+) -> (Vec<G::EdgeRef>, Vec<Vec<NodeIndex<Ix>>>)
+where
+    G: IntoEdgeReferences + NodeIndexable,
+    G::EdgeRef: LineGraphEdgeRef,
+    NF: FnMut(G::EdgeRef) -> NW,
+    Ix: IndexType,
+{
+    let mut edges = Vec::with_capacity(line_graph.capacity().0);
+    let mut incident = vec![Vec::new(); g.node_bound()];
+    for edge in edge_references(g) {
+        let n = line_graph.add_node(node_fn(edge));
+        on_node(edge);
+        edges.push(edge);
+        let s = NodeIndexable::to_index(&g, edge.source());
+        let t = NodeIndexable::to_index(&g, edge.target());
+        if s != t {
+            incident[s].push(n);
+            incident[t].push(n);
+        } else {
+            match loops {
+                LoopPolicy::Ignore => {}
+                LoopPolicy::Once => incident[s].push(n),
+                LoopPolicy::Twice => {
+                    incident[s].push(n);
+                    incident[s].push(n);
+                }
             }
         }
     }
+    (edges, incident)
+}
 
-    for node in g.edge_references() {
-        let id = {
-            use petgraph::visit::EdgeIndexable;
-            g.to_index(node.id())
-        };
-        let id = {
-            use petgraph::visit::NodeIndexable;
-            line_graph.from_index(id)
-        };
-
-        let weight = line_graph.node_weight_mut(id).unwrap();
-        *weight = node.weight().clone();
+/// Connect the line graph nodes of the edges incident to each vertex
+///
+/// `on_edge` is called with the shared vertex of each added edge.
+fn add_line_edges<G, NW, EW, EF, Ix>(
+    g: G,
+    line_graph: &mut UnGraph<NW, EW, Ix>,
+    edges: &[G::EdgeRef],
+    incident: &[Vec<NodeIndex<Ix>>],
+    mut edge_fn: EF,
+    mut on_edge: impl FnMut(G::NodeId),
+) where
+    G: IntoNodeReferences + IntoEdgeReferences + NodeIndexable,
+    EF: FnMut(G::EdgeRef, G::EdgeRef, G::NodeRef) -> EW,
+    Ix: IndexType,
+{
+    for node in g.node_references() {
+        let nidx = node.id();
+        let incident = &incident[NodeIndexable::to_index(&g, nidx)];
+        for (s, &v1) in incident.iter().enumerate() {
+            for &v2 in &incident[s + 1..] {
+                let e1 = edges[v1.index()];
+                let e2 = edges[v2.index()];
+                line_graph.add_edge(v1, v2, edge_fn(e1, e2, node));
+                on_edge(nidx);
+            }
+        }
     }
-    line_graph
+}
+
+/// Construct the line graph for a `StableGraph` `g` together with the
+/// correspondence between the line graph and `g`
+///
+/// This is the same as [line_graph_with_map], except that the line
+/// graph is returned as a `StableGraph`. Removed edges of `g` are
+/// skipped, so the indices of the line graph nodes generally differ
+/// from the indices of the original edges. Use
+/// [LineGraphMap::line_node] to find the line graph node
+/// corresponding to an edge. The line graph has the same index type as
+/// `g`.
+///
+/// # Example
+///
+/// ```rust
+/// use line_graph::stable_line_graph;
+/// use petgraph::stable_graph::{EdgeIndex, StableUnGraph};
+///
+/// let mut g = StableUnGraph::<(), ()>::default();
+/// let n: Vec<_> = (0..3).map(|_| g.add_node(())).collect();
+/// let e0 = g.add_edge(n[0], n[1], ());
+/// let e1 = g.add_edge(n[1], n[2], ());
+/// let e2 = g.add_edge(n[2], n[0], ());
+/// g.remove_edge(e1);
+///
+/// let (g_line, map) = stable_line_graph(&g);
+/// assert_eq!(g_line.node_count(), 2);
+/// assert_eq!(map.line_node(e1), None);
+/// let (v0, v2) = (map.line_node(e0).unwrap(), map.line_node(e2).unwrap());
+/// assert!(g_line.contains_edge(v0, v2));
+/// ```
+#[allow(clippy::type_complexity)]
+pub fn stable_line_graph<N, E, Ix>(
+    g: &StableUnGraph<N, E, Ix>,
+) -> (
+    StableUnGraph<E, N, Ix>,
+    LineGraphMap<NodeIndex<Ix>, EdgeIndex<Ix>, Ix>,
+)
+where
+    N: Clone,
+    E: Clone,
+    Ix: IndexType,
+{
+    let (line_graph, map) =
+        LineGraphBuilder::new().index_type::<Ix>().build_with_map(g);
+    (StableGraph::from(line_graph), map)
+}
+
+/// Construct the directed line graph for `g`
+///
+/// Each edge `e` of `g` becomes the node with index `e.index()` in
+/// the line graph. For each pair of edges `e1 = (u, v)`, `e2 = (v, w)`
+/// there is an edge from `e1` to `e2` with the weight of `v`. The line
+/// graph has the same index type as `g`.
+pub fn line_digraph<N, E, Ix>(g: &DiGraph<N, E, Ix>) -> DiGraph<E, N, Ix>
+where
+    N: Clone,
+    E: Clone,
+    Ix: IndexType,
+{
+    edge_expanded_graph(g, |_, _, (_, w)| Some(w.clone()))
 }
 
 #[cfg(test)]
@@ -119,4 +659,191 @@ mod tests {
         ]);
         assert!(is_isomorphic(&target, &line_graph(&orig)));
     }
+
+    #[test]
+    fn map() {
+        let orig = UnGraph::<(), ()>::from_edges([
+            (0, 1),
+            (0, 2),
+            (0, 3),
+            (1, 4),
+            (2, 3),
+            (3, 4),
+        ]);
+        let (line, map) = line_graph_with_map(&orig);
+        for e in orig.edge_indices() {
+            let n = map.line_node(e).unwrap();
+            assert_eq!(map.edge(n), Some(e));
+            assert_eq!(map.endpoints(n), orig.edge_endpoints(e));
+        }
+        for e in line.edge_references() {
+            let v = map.vertex(e.id()).unwrap();
+            for n in [e.source(), e.target()] {
+                let (a, b) = map.endpoints(n).unwrap();
+                assert!(a == v || b == v);
+            }
+        }
+    }
+
+    #[test]
+    fn stable_with_holes() {
+        let mut orig = StableUnGraph::<u32, u32>::default();
+        let n: Vec<_> = (0..5).map(|i| orig.add_node(i)).collect();
+        let mut edges = Vec::new();
+        for (i, (a, b)) in [(0, 1), (0, 2), (0, 3), (1, 4), (2, 3), (3, 4)]
+            .into_iter()
+            .enumerate()
+        {
+            edges.push(orig.add_edge(n[a], n[b], i as u32));
+        }
+        orig.remove_edge(edges[0]);
+        orig.remove_edge(edges[4]);
+        orig.remove_node(n[2]);
+
+        let (line, map) = stable_line_graph(&orig);
+        assert_eq!(line.node_count(), orig.edge_count());
+        for e in orig.edge_indices() {
+            let n = map.line_node(e).unwrap();
+            assert_eq!(line[n], orig[e]);
+            assert_eq!(map.endpoints(n), orig.edge_endpoints(e));
+        }
+        for e in [edges[0], edges[1], edges[4]] {
+            assert_eq!(map.line_node(e), None);
+        }
+
+        let target = UnGraph::<(), ()>::from_edges([(0, 1), (1, 2)]);
+        assert!(is_isomorphic(&target, &UnGraph::from(line)));
+    }
+
+    #[test]
+    fn custom_weights() {
+        let orig = UnGraph::<u32, u32>::from_edges([
+            (0, 1, 1),
+            (1, 2, 2),
+            (2, 0, 4),
+            (2, 3, 8),
+        ]);
+        let line = line_graph_with(
+            &orig,
+            |e| (e.id(), e.weight() + 1),
+            |e1, e2, v| e1.weight() + e2.weight() + 100 * v.id().index() as u32,
+        );
+        assert_eq!(line.node_count(), orig.edge_count());
+        for n in line.node_indices() {
+            let (e, w) = line[n];
+            assert_eq!(e.index(), n.index());
+            assert_eq!(w, orig[e] + 1);
+        }
+        let mut weights: Vec<_> = line.edge_weights().copied().collect();
+        weights.sort();
+        assert_eq!(weights, [5, 103, 206, 210, 212]);
+    }
+
+    #[test]
+    fn simple_dipole() {
+        let orig = UnGraph::<u32, ()>::from_edges([(0, 1), (0, 1), (0, 1)]);
+        let target = UnGraph::<(), ()>::from_edges([(0, 1), (0, 2), (1, 2)]);
+        let line = simple_line_graph(&orig);
+        assert!(is_isomorphic(&target, &line));
+        assert!(line.edge_weights().all(|w| w.len() == 2));
+    }
+
+    #[test]
+    fn generic_input() {
+        use petgraph::{
+            csr::Csr,
+            graphmap::UnGraphMap,
+            visit::{EdgeFiltered, NodeFiltered, Reversed},
+        };
+
+        let edges = [(0, 1), (0, 2), (0, 3), (1, 4), (2, 3), (3, 4)];
+        let target = UnGraph::<(), ()>::from_edges([
+            (0, 1),
+            (0, 2),
+            (0, 3),
+            (1, 2),
+            (1, 4),
+            (2, 4),
+            (2, 5),
+            (3, 5),
+            (4, 5),
+        ]);
+        let orig = UnGraphMap::<u32, ()>::from_edges(edges);
+        assert!(is_isomorphic(&target, &line_graph(&orig)));
+
+        let orig = UnGraph::<(), ()>::from_edges(edges);
+        assert!(is_isomorphic(&target, &line_graph(Reversed(&orig))));
+
+        let target = UnGraph::<(), ()>::from_edges([(0, 1), (1, 2)]);
+        let filtered = NodeFiltered::from_fn(&orig, |n| n.index() != 0);
+        assert!(is_isomorphic(&target, &line_graph(&filtered)));
+
+        let target = UnGraph::<(), ()>::from_edges([
+            (0, 1),
+            (0, 3),
+            (1, 3),
+            (1, 4),
+            (2, 4),
+            (3, 4),
+        ]);
+        let filtered = EdgeFiltered::from_fn(&orig, |e| e.id().index() != 0);
+        let (line, map) = line_graph_with_map(&filtered);
+        assert!(is_isomorphic(&target, &line));
+        assert_eq!(map.line_node(EdgeIndex::new(0)), None);
+
+        let mut csr = Csr::<(), (), Undirected>::new();
+        for _ in 0..5 {
+            csr.add_node(());
+        }
+        for (a, b) in edges {
+            csr.add_edge(a, b, ());
+        }
+        assert!(is_isomorphic(&line_graph(&orig), &line_graph(&csr)));
+        let one_way = EdgeFiltered::from_fn(&csr, |e| e.source() <= e.target());
+        assert!(is_isomorphic(&line_graph(&orig), &line_graph(&one_way)));
+        assert!(is_isomorphic(
+            &line_graph(&orig),
+            &line_graph(Reversed(&csr))
+        ));
+
+        let mut path = Csr::<(), (), Undirected>::new();
+        for _ in 0..3 {
+            path.add_node(());
+        }
+        path.add_edge(0, 1, ());
+        path.add_edge(1, 2, ());
+        let line = line_graph(&path);
+        assert_eq!((line.node_count(), line.edge_count()), (2, 1));
+        assert_eq!(LineGraphBuilder::new().edge_count(&path), 1);
+    }
+
+    #[test]
+    fn directed_cycle() {
+        let orig = DiGraph::<(), ()>::from_edges([(0, 1), (1, 2), (2, 0)]);
+        assert!(is_isomorphic(&orig, &line_digraph(&orig)));
+        let orig = DiGraph::<(), (), u16>::from_edges([(0, 1), (1, 2), (2, 0)]);
+        let line: DiGraph<(), (), u16> = line_digraph(&orig);
+        assert!(is_isomorphic(&orig, &line));
+    }
+
+    // the line graph of the de Bruijn graph B(2, n) is B(2, n + 1)
+    #[test]
+    fn de_bruijn() {
+        let b1 =
+            DiGraph::<(), ()>::from_edges([(0, 0), (0, 1), (1, 0), (1, 1)]);
+        let b2 = DiGraph::<(), ()>::from_edges([
+            (0, 0),
+            (0, 1),
+            (1, 2),
+            (1, 3),
+            (2, 0),
+            (2, 1),
+            (3, 2),
+            (3, 3),
+        ]);
+        let line = line_digraph(&b1);
+        assert!(is_isomorphic(&b2, &line));
+        assert_eq!(line.capacity(), (line.node_count(), line.edge_count()));
+        assert!(is_isomorphic(&line_digraph(&b2), &line_digraph(&line)));
+    }
 }
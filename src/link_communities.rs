@@ -0,0 +1,351 @@
+//! Link communities via the line graph
+//!
+//! Following [Ahn, Bagrow, and Lehmann](https://doi.org/10.1038/nature09182),
+//! communities of edges instead of vertices reveal overlapping
+//! communities: a vertex belongs to all communities of its edges.
+//!
+//! [jaccard_line_graph] constructs the line graph weighted by the
+//! similarities of adjacent edges. [single_linkage] clusters the edges
+//! hierarchically based on these similarities, and [link_communities]
+//! cuts the resulting dendrogram where the [partition_density] is
+//! maximal.
+//!
+//! # Example
+//!
+//! Two triangles sharing a vertex form two link communities.
+//!
+//! ```rust
+//! use line_graph::link_communities::link_communities;
+//! use petgraph::graph::{EdgeIndex, UnGraph};
+//!
+//! let g = UnGraph::<(), ()>::from_edges([
+//!     (0, 1), (1, 2), (2, 0), (2, 3), (3, 4), (4, 2)
+//! ]);
+//! let (communities, density) = link_communities(&g);
+//! assert_eq!(density, 1.);
+//! let community = |e| communities[&EdgeIndex::new(e)];
+//! assert_eq!(community(0), community(2));
+//! assert_ne!(community(2), community(3));
+//! ```
+use crate::line_graph_with;
+
+use petgraph::{
+    graph::{EdgeIndex, EdgeReference, IndexType, UnGraph},
+    visit::EdgeRef,
+};
+use std::collections::{HashMap, HashSet};
+
+/// Construct the line graph of `g` weighted by edge similarities
+///
+/// Each line graph node corresponds to the edge of `g` with the same
+/// index and has that index as weight. Two adjacent edges `(i, k)` and
+/// `(j, k)` are connected by a line graph edge whose weight is the
+/// Jaccard similarity
+///
+/// `|n(i) ∩ n(j)| / |n(i) ∪ n(j)|`
+///
+/// of the inclusive neighbourhoods `n(i)` and `n(j)`, i.e. the sets of
+/// neighbours including the vertices themselves. The similarity is
+/// intended for simple graphs. As in [line_graph](crate::line_graph),
+/// edges sharing two vertices are connected twice.
+///
+/// # Example
+///
+/// ```rust
+/// use line_graph::link_communities::jaccard_line_graph;
+/// use petgraph::graph::UnGraph;
+///
+/// // n(0) = {0, 1, 2}, n(3) = {1, 2, 3}
+/// let g = UnGraph::<(), ()>::from_edges([(0, 1), (0, 2), (1, 3), (2, 3)]);
+/// let line = jaccard_line_graph(&g);
+/// let e = line.find_edge(0.into(), 2.into()).unwrap();
+/// assert_eq!(line[e], 0.5);
+/// ```
+pub fn jaccard_line_graph<N, E, Ix: IndexType>(
+    g: &UnGraph<N, E, Ix>,
+) -> UnGraph<EdgeIndex<Ix>, f64, Ix> {
+    let mut neighbourhoods: Vec<_> =
+        g.node_indices().map(|v| vec![v.index()]).collect();
+    for e in g.edge_references() {
+        let (s, t) = (e.source().index(), e.target().index());
+        neighbourhoods[s].push(t);
+        neighbourhoods[t].push(s);
+    }
+    for n in &mut neighbourhoods {
+        n.sort_unstable();
+        n.dedup();
+    }
+    line_graph_with(
+        g,
+        |e| e.id(),
+        |e1, e2, (v, _)| {
+            let other = |e: EdgeReference<E, Ix>| {
+                if e.source() == v {
+                    e.target()
+                } else {
+                    e.source()
+                }
+            };
+            let (i, j) = (other(e1).index(), other(e2).index());
+            jaccard(&neighbourhoods[i], &neighbourhoods[j])
+        },
+    )
+}
+
+// Jaccard similarity of two sorted sets
+fn jaccard(a: &[usize], b: &[usize]) -> f64 {
+    let (mut i, mut j, mut common) = (0, 0, 0);
+    while i < a.len() && j < b.len() {
+        match a[i].cmp(&b[j]) {
+            std::cmp::Ordering::Less => i += 1,
+            std::cmp::Ordering::Greater => j += 1,
+            std::cmp::Ordering::Equal => {
+                common += 1;
+                i += 1;
+                j += 1;
+            }
+        }
+    }
+    common as f64 / (a.len() + b.len() - common) as f64
+}
+
+/// Cluster the edges of `g` by single-linkage hierarchical clustering
+///
+/// Initially, each edge forms its own cluster. Clusters are merged in
+/// order of decreasing similarity of the most similar pair of adjacent
+/// edges, as given by the [jaccard_line_graph]. Each entry of the
+/// returned dendrogram is a merge `(similarity, e1, e2)` of the
+/// clusters containing `e1` and `e2`. Clusters of edges that are not
+/// connected in `g` are never merged.
+///
+/// Merges with the same similarity are ordered by the indices of the
+/// line graph edges.
+pub fn single_linkage<N, E, Ix: IndexType>(
+    g: &UnGraph<N, E, Ix>,
+) -> Vec<(f64, EdgeIndex<Ix>, EdgeIndex<Ix>)> {
+    let line = jaccard_line_graph(g);
+    let mut links: Vec<_> = line.edge_references().collect();
+    links.sort_by(|a, b| b.weight().total_cmp(a.weight()));
+
+    let mut clusters = UnionFind::new(g.edge_count());
+    let mut dendrogram = Vec::with_capacity(g.edge_count());
+    for link in links {
+        let (e1, e2) = (line[link.source()], line[link.target()]);
+        if clusters.union(e1.index(), e2.index()) {
+            dendrogram.push((*link.weight(), e1, e2));
+        }
+    }
+    dendrogram
+}
+
+/// The partition density of the edge communities of `g`
+///
+/// `communities` assigns a community to each edge. The partition
+/// density is
+///
+/// `D = 2 / M Σ_c m_c (m_c - n_c + 1) / ((n_c - 2) (n_c - 1))`,
+///
+/// where `M` is the number of edges, `m_c` the number of edges in
+/// community `c`, and `n_c` the number of vertices incident to these
+/// edges. Communities with only two vertices do not contribute. Edges
+/// without a community are ignored in the sum.
+pub fn partition_density<N, E, Ix: IndexType>(
+    g: &UnGraph<N, E, Ix>,
+    communities: &HashMap<EdgeIndex<Ix>, usize>,
+) -> f64 {
+    let mut sizes: HashMap<usize, (usize, HashSet<usize>)> = HashMap::new();
+    for e in g.edge_references() {
+        if let Some(&c) = communities.get(&e.id()) {
+            let (m, vertices) = sizes.entry(c).or_default();
+            *m += 1;
+            vertices.extend([e.source().index(), e.target().index()]);
+        }
+    }
+    let total: f64 = sizes
+        .values()
+        .map(|(m, vertices)| density_term(*m, vertices.len()))
+        .sum();
+    if g.edge_count() == 0 {
+        0.
+    } else {
+        2. * total / g.edge_count() as f64
+    }
+}
+
+fn density_term(m: usize, n: usize) -> f64 {
+    if n <= 2 {
+        return 0.;
+    }
+    let (m, n) = (m as f64, n as f64);
+    m * (m - n + 1.) / ((n - 2.) * (n - 1.))
+}
+
+/// Find the link communities of `g` with maximum partition density
+///
+/// Cuts the [single_linkage] dendrogram at the similarity threshold
+/// where the [partition_density] is maximal. Merges with the same
+/// similarity are either all performed or none of them. If several
+/// thresholds give the same partition density, the highest one is
+/// chosen.
+///
+/// Returns the community of each edge together with the partition
+/// density. Communities are numbered consecutively from zero in the
+/// order of their lowest edge index.
+pub fn link_communities<N, E, Ix: IndexType>(
+    g: &UnGraph<N, E, Ix>,
+) -> (HashMap<EdgeIndex<Ix>, usize>, f64) {
+    let dendrogram = single_linkage(g);
+
+    let mut clusters = UnionFind::new(g.edge_count());
+    let mut vertices: Vec<HashSet<usize>> = g
+        .edge_references()
+        .map(|e| HashSet::from([e.source().index(), e.target().index()]))
+        .collect();
+    let mut edges = vec![1; g.edge_count()];
+    let mut total: f64 =
+        vertices.iter().map(|v| density_term(1, v.len())).sum();
+    let (mut best_total, mut best_merges) = (total, 0);
+    for (pos, &(similarity, e1, e2)) in dendrogram.iter().enumerate() {
+        let (r1, r2) = (clusters.find(e1.index()), clusters.find(e2.index()));
+        total -= density_term(edges[r1], vertices[r1].len());
+        total -= density_term(edges[r2], vertices[r2].len());
+        clusters.union(r1, r2);
+        let (root, other) = if clusters.find(r1) == r1 {
+            (r1, r2)
+        } else {
+            (r2, r1)
+        };
+        let mut other_vertices = std::mem::take(&mut vertices[other]);
+        if other_vertices.len() > vertices[root].len() {
+            std::mem::swap(&mut vertices[root], &mut other_vertices);
+        }
+        vertices[root].extend(other_vertices);
+        edges[root] += edges[other];
+        total += density_term(edges[root], vertices[root].len());
+
+        let level_done = dendrogram
+            .get(pos + 1)
+            .map_or(true, |next| next.0 != similarity);
+        if level_done && total > best_total {
+            (best_total, best_merges) = (total, pos + 1);
+        }
+    }
+
+    let mut clusters = UnionFind::new(g.edge_count());
+    for &(_, e1, e2) in &dendrogram[..best_merges] {
+        clusters.union(e1.index(), e2.index());
+    }
+    let mut ids = HashMap::new();
+    let communities = g
+        .edge_indices()
+        .map(|e| {
+            let root = clusters.find(e.index());
+            let next = ids.len();
+            (e, *ids.entry(root).or_insert(next))
+        })
+        .collect();
+    let density = if g.edge_count() == 0 {
+        0.
+    } else {
+        2. * best_total / g.edge_count() as f64
+    };
+    (communities, density)
+}
+
+// Disjoint sets with path halving and union by size
+struct UnionFind {
+    parent: Vec<usize>,
+    size: Vec<usize>,
+}
+
+impl UnionFind {
+    fn new(n: usize) -> Self {
+        Self {
+            parent: (0..n).collect(),
+            size: vec![1; n],
+        }
+    }
+
+    fn find(&mut self, mut x: usize) -> usize {
+        while self.parent[x] != x {
+            self.parent[x] = self.parent[self.parent[x]];
+            x = self.parent[x];
+        }
+        x
+    }
+
+    // Returns whether `x` and `y` were in different sets
+    fn union(&mut self, x: usize, y: usize) -> bool {
+        let (mut x, mut y) = (self.find(x), self.find(y));
+        if x == y {
+            return false;
+        }
+        if self.size[x] < self.size[y] {
+            std::mem::swap(&mut x, &mut y);
+        }
+        self.parent[y] = x;
+        self.size[x] += self.size[y];
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn two_triangles() -> UnGraph<(), ()> {
+        UnGraph::from_edges([(0, 1), (1, 2), (2, 0), (2, 3), (3, 4), (4, 2)])
+    }
+
+    #[test]
+    fn similarities() {
+        let g = two_triangles();
+        let line = jaccard_line_graph(&g);
+        assert_eq!(line.edge_count(), 10);
+        let weight = |a: u32, b: u32| {
+            let e = line.find_edge(a.into(), b.into()).unwrap();
+            line[e]
+        };
+        // n(0) = n(1) = {0, 1, 2}, n(2) = {0, 1, 2, 3, 4}
+        assert_eq!(weight(0, 1), 0.6);
+        assert_eq!(weight(1, 2), 1.);
+        assert_eq!(weight(1, 3), 0.2);
+        assert_eq!(weight(3, 4), 0.6);
+        assert_eq!(weight(3, 5), 1.);
+        assert!(line.node_weights().copied().eq(g.edge_indices()));
+    }
+
+    #[test]
+    fn dendrogram() {
+        let mut g = two_triangles();
+        g.extend_with_edges([(5, 6)]);
+        let dendrogram = single_linkage(&g);
+        assert_eq!(dendrogram.len(), 5);
+        assert!(dendrogram.windows(2).all(|w| w[0].0 >= w[1].0));
+        assert_eq!(dendrogram[0].0, 1.);
+        assert_eq!(dendrogram[4].0, 0.2);
+        assert!(dendrogram
+            .iter()
+            .all(|&(_, e1, e2)| e1.index() < 6 && e2.index() < 6));
+    }
+
+    #[test]
+    fn communities() {
+        let mut g = two_triangles();
+        g.extend_with_edges([(5, 6)]);
+        let (communities, density) = link_communities(&g);
+        let communities: Vec<_> =
+            g.edge_indices().map(|e| communities[&e]).collect();
+        assert_eq!(communities, [0, 0, 0, 1, 1, 1, 2]);
+        assert_eq!(density, 2. * 3. / 7.);
+
+        let all = g.edge_indices().map(|e| (e, 0)).collect();
+        let expected = 2. / 7. * density_term(7, 7);
+        assert_eq!(partition_density(&g, &all), expected);
+
+        let (communities, density) =
+            link_communities(&UnGraph::<(), ()>::default());
+        assert!(communities.is_empty());
+        assert_eq!(density, 0.);
+    }
+}
@@ -0,0 +1,88 @@
+use petgraph::graph::{DefaultIx, EdgeIndex, IndexType, NodeIndex};
+
+/// Correspondence between a line graph and its original graph
+///
+/// Each node of the line graph corresponds to an edge of the original
+/// graph and each edge of the line graph corresponds to the vertex
+/// shared by the two original edges. `N` and `E` are the node and
+/// edge identifiers of the original graph and `Ix` is the index type of
+/// the line graph.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct LineGraphMap<N = NodeIndex, E = EdgeIndex, Ix = DefaultIx> {
+    pub(crate) nodes: Vec<(E, N, N)>,
+    pub(crate) edges: Vec<N>,
+    pub(crate) line_nodes: Vec<Option<NodeIndex<Ix>>>,
+}
+
+impl<N, E, Ix> Default for LineGraphMap<N, E, Ix> {
+    fn default() -> Self {
+        Self {
+            nodes: Vec::new(),
+            edges: Vec::new(),
+            line_nodes: Vec::new(),
+        }
+    }
+}
+
+impl<N: Copy, E: Copy, Ix: IndexType> LineGraphMap<N, E, Ix> {
+    pub(crate) fn with_capacity(nodes: usize, edges: usize) -> Self {
+        Self {
+            nodes: Vec::with_capacity(nodes),
+            edges: Vec::with_capacity(edges),
+            line_nodes: Vec::with_capacity(nodes),
+        }
+    }
+
+    pub(crate) fn add_node(
+        &mut self,
+        edge: E,
+        edge_index: usize,
+        source: N,
+        target: N,
+    ) -> NodeIndex<Ix> {
+        let n = NodeIndex::new(self.nodes.len());
+        self.nodes.push((edge, source, target));
+        if edge_index >= self.line_nodes.len() {
+            self.line_nodes.resize(edge_index + 1, None);
+        }
+        self.line_nodes[edge_index] = Some(n);
+        n
+    }
+
+    pub(crate) fn add_edge(&mut self, vertex: N) {
+        self.edges.push(vertex);
+    }
+
+    /// The original edge corresponding to the line graph node `n`
+    pub fn edge(&self, n: NodeIndex<Ix>) -> Option<E> {
+        self.nodes.get(n.index()).map(|(e, _, _)| *e)
+    }
+
+    /// The endpoints of the original edge corresponding to the line
+    /// graph node `n`
+    pub fn endpoints(&self, n: NodeIndex<Ix>) -> Option<(N, N)> {
+        self.nodes.get(n.index()).map(|(_, s, t)| (*s, *t))
+    }
+
+    /// The original vertex shared by the two original edges joined by
+    /// the line graph edge `e`
+    pub fn vertex(&self, e: EdgeIndex<Ix>) -> Option<N> {
+        self.edges.get(e.index()).copied()
+    }
+
+    /// The line graph node corresponding to the original edge with
+    /// index `i`, as given by
+    /// [EdgeIndexable::to_index](petgraph::visit::EdgeIndexable::to_index)
+    pub fn line_node_by_index(&self, i: usize) -> Option<NodeIndex<Ix>> {
+        self.line_nodes.get(i).copied().flatten()
+    }
+}
+
+impl<Ix: IndexType, LIx: IndexType>
+    LineGraphMap<NodeIndex<Ix>, EdgeIndex<Ix>, LIx>
+{
+    /// The line graph node corresponding to the original edge `e`
+    pub fn line_node(&self, e: EdgeIndex<Ix>) -> Option<NodeIndex<LIx>> {
+        self.line_node_by_index(e.index())
+    }
+}
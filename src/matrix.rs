@@ -0,0 +1,434 @@
+use crate::oriented::{non_backtracking_pairs, outgoing_half_edges};
+
+use std::ops::{Add, Mul};
+
+use petgraph::{
+    graph::{IndexType, UnGraph},
+    visit::EdgeRef,
+};
+
+/// A sparse matrix in compressed sparse row (CSR) format
+///
+/// The column indices of the entries in row `i` are
+/// `indices()[indptr()[i]..indptr()[i + 1]]` in ascending order and
+/// their values are the corresponding elements of `values()`. Entries
+/// equal to `T::default()` are not stored.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct CsrMatrix<T> {
+    rows: usize,
+    cols: usize,
+    indptr: Vec<usize>,
+    indices: Vec<usize>,
+    values: Vec<T>,
+}
+
+impl<T> CsrMatrix<T>
+where
+    T: Copy + Default + PartialEq + Add<Output = T>,
+{
+    /// Construct a `rows` × `cols` matrix from `(row, column, value)`
+    /// triplets
+    ///
+    /// Values for the same position are added.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any position is out of bounds.
+    pub fn from_triplets<I>(rows: usize, cols: usize, triplets: I) -> Self
+    where
+        I: IntoIterator<Item = (usize, usize, T)>,
+    {
+        let mut triplets: Vec<_> = triplets.into_iter().collect();
+        triplets.sort_by_key(|&(i, j, _)| (i, j));
+        let mut entries: Vec<(usize, usize, T)> =
+            Vec::with_capacity(triplets.len());
+        for (i, j, v) in triplets {
+            assert!(i < rows && j < cols, "matrix index out of bounds");
+            match entries.last_mut() {
+                Some(last) if (last.0, last.1) == (i, j) => last.2 = last.2 + v,
+                _ => entries.push((i, j, v)),
+            }
+        }
+        entries.retain(|(_, _, v)| *v != T::default());
+
+        let mut indptr = vec![0; rows + 1];
+        for &(i, _, _) in &entries {
+            indptr[i + 1] += 1;
+        }
+        for i in 0..rows {
+            indptr[i + 1] += indptr[i];
+        }
+        Self {
+            rows,
+            cols,
+            indptr,
+            indices: entries.iter().map(|&(_, j, _)| j).collect(),
+            values: entries.into_iter().map(|(_, _, v)| v).collect(),
+        }
+    }
+}
+
+impl<T> CsrMatrix<T> {
+    /// The number of rows
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    /// The number of columns
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    /// The number of stored entries
+    pub fn nnz(&self) -> usize {
+        self.values.len()
+    }
+
+    /// The offsets of the rows in [indices](Self::indices) and
+    /// [values](Self::values)
+    pub fn indptr(&self) -> &[usize] {
+        &self.indptr
+    }
+
+    /// The column indices of the stored entries
+    pub fn indices(&self) -> &[usize] {
+        &self.indices
+    }
+
+    /// The values of the stored entries
+    pub fn values(&self) -> &[T] {
+        &self.values
+    }
+
+    /// The stored entries in row `i` as `(column, value)` pairs
+    pub fn row(&self, i: usize) -> impl Iterator<Item = (usize, &T)> {
+        let range = self.indptr[i]..self.indptr[i + 1];
+        self.indices[range.clone()]
+            .iter()
+            .copied()
+            .zip(&self.values[range])
+    }
+
+    /// The entry in row `i` and column `j`, if it is stored
+    pub fn get(&self, i: usize, j: usize) -> Option<&T> {
+        if i >= self.rows {
+            return None;
+        }
+        let range = self.indptr[i]..self.indptr[i + 1];
+        let pos = self.indices[range.clone()].binary_search(&j).ok()?;
+        Some(&self.values[range.start + pos])
+    }
+}
+
+impl<T: Copy> CsrMatrix<T> {
+    fn entries(&self) -> impl Iterator<Item = (usize, usize, T)> + '_ {
+        (0..self.rows)
+            .flat_map(move |i| self.row(i).map(move |(j, &v)| (i, j, v)))
+    }
+
+    /// The transposed matrix
+    pub fn transpose(&self) -> Self {
+        let mut indptr = vec![0; self.cols + 1];
+        for &j in &self.indices {
+            indptr[j + 1] += 1;
+        }
+        for j in 0..self.cols {
+            indptr[j + 1] += indptr[j];
+        }
+        let mut next = indptr.clone();
+        let mut indices = vec![0; self.nnz()];
+        let mut values = self.values.clone();
+        for i in 0..self.rows {
+            for (j, &v) in self.row(i) {
+                indices[next[j]] = i;
+                values[next[j]] = v;
+                next[j] += 1;
+            }
+        }
+        Self {
+            rows: self.cols,
+            cols: self.rows,
+            indptr,
+            indices,
+            values,
+        }
+    }
+}
+
+impl<T> Add for &CsrMatrix<T>
+where
+    T: Copy + Default + PartialEq + Add<Output = T>,
+{
+    type Output = CsrMatrix<T>;
+
+    /// The matrix sum
+    ///
+    /// # Panics
+    ///
+    /// Panics if the dimensions of `self` and `rhs` differ.
+    fn add(self, rhs: Self) -> CsrMatrix<T> {
+        assert_eq!(
+            (self.rows, self.cols),
+            (rhs.rows, rhs.cols),
+            "incompatible matrix dimensions"
+        );
+        CsrMatrix::from_triplets(
+            self.rows,
+            self.cols,
+            self.entries().chain(rhs.entries()),
+        )
+    }
+}
+
+impl<T> Mul for &CsrMatrix<T>
+where
+    T: Copy + Default + PartialEq + Add<Output = T> + Mul<Output = T>,
+{
+    type Output = CsrMatrix<T>;
+
+    /// The matrix product
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of columns of `self` differs from the number
+    /// of rows of `rhs`.
+    fn mul(self, rhs: Self) -> CsrMatrix<T> {
+        assert_eq!(self.cols, rhs.rows, "incompatible matrix dimensions");
+        let mut indptr = Vec::with_capacity(self.rows + 1);
+        indptr.push(0);
+        let mut indices = Vec::new();
+        let mut values = Vec::new();
+        let mut row = vec![None; rhs.cols];
+        let mut cols = Vec::new();
+        for i in 0..self.rows {
+            for (k, &a) in self.row(i) {
+                for (j, &b) in rhs.row(k) {
+                    match &mut row[j] {
+                        Some(v) => *v = *v + a * b,
+                        entry => {
+                            *entry = Some(a * b);
+                            cols.push(j);
+                        }
+                    }
+                }
+            }
+            cols.sort_unstable();
+            for j in cols.drain(..) {
+                let v = row[j].take().unwrap();
+                if v != T::default() {
+                    indices.push(j);
+                    values.push(v);
+                }
+            }
+            indptr.push(indices.len());
+        }
+        CsrMatrix {
+            rows: self.rows,
+            cols: rhs.cols,
+            indptr,
+            indices,
+            values,
+        }
+    }
+}
+
+/// The unsigned incidence matrix `B` of `g`
+///
+/// Row `i` corresponds to the vertex with index `i` and column `j` to
+/// the edge with index `j`. The entry is 1 if the edge is incident to
+/// the vertex and 2 if it is a self-loop at the vertex.
+///
+/// # Example
+///
+/// ```rust
+/// use line_graph::incidence_matrix;
+/// use petgraph::graph::UnGraph;
+///
+/// let g = UnGraph::<(), ()>::from_edges([(0, 1), (1, 1)]);
+/// let b = incidence_matrix(&g);
+/// assert_eq!(b.get(0, 0), Some(&1));
+/// assert_eq!(b.get(1, 1), Some(&2));
+/// assert_eq!(b.get(0, 1), None);
+/// ```
+pub fn incidence_matrix<N, E, Ix: IndexType>(
+    g: &UnGraph<N, E, Ix>,
+) -> CsrMatrix<usize> {
+    let triplets = g.edge_references().flat_map(|e| {
+        let j = e.id().index();
+        [(e.source().index(), j, 1), (e.target().index(), j, 1)]
+    });
+    CsrMatrix::from_triplets(g.node_count(), g.edge_count(), triplets)
+}
+
+/// The adjacency matrix `A(L) = BᵀB - 2I` of the line graph of `g`
+///
+/// Here, `B` is the [incidence_matrix] of `g`. Row and column `i`
+/// correspond to the line graph node for the edge with index `i`. Off
+/// the diagonal, the entries are the numbers of vertices shared by two
+/// edges. On the diagonal, there is an entry 2 for each self-loop in
+/// `g`. This is the adjacency matrix of the line graph constructed with
+/// [LoopPolicy::Twice](crate::LoopPolicy::Twice), where each line graph
+/// self-loop counts twice.
+///
+/// # Example
+///
+/// ```rust
+/// use line_graph::{incidence_matrix, line_graph_adjacency_matrix};
+/// use petgraph::graph::UnGraph;
+///
+/// let g = UnGraph::<(), ()>::from_edges([(0, 1), (1, 2), (2, 0), (1, 1)]);
+/// let b = incidence_matrix(&g);
+/// let btb = &b.transpose() * &b;
+/// let a = line_graph_adjacency_matrix(&g);
+/// assert_eq!(btb.get(0, 1), a.get(0, 1));
+/// assert_eq!(btb.get(3, 3), Some(&4));
+/// assert_eq!(a.get(3, 3), Some(&2));
+/// ```
+pub fn line_graph_adjacency_matrix<N, E, Ix: IndexType>(
+    g: &UnGraph<N, E, Ix>,
+) -> CsrMatrix<usize> {
+    let mut triplets = Vec::new();
+    let mut incident = Vec::new();
+    for v in g.node_indices() {
+        incident.clear();
+        incident.extend(g.edges(v).map(|e| {
+            let multiplicity = if e.source() == e.target() { 2 } else { 1 };
+            (e.id().index(), multiplicity)
+        }));
+        for (a, &(e, m)) in incident.iter().enumerate() {
+            if m == 2 {
+                triplets.push((e, e, 2));
+            }
+            for &(f, n) in &incident[a + 1..] {
+                triplets.push((e, f, m * n));
+                triplets.push((f, e, m * n));
+            }
+        }
+    }
+    CsrMatrix::from_triplets(g.edge_count(), g.edge_count(), triplets)
+}
+
+/// The Hashimoto non-backtracking matrix of `g`
+///
+/// Row and column `2 * i` correspond to the edge with index `i`
+/// oriented from its source to its target, and row and column `2 * i +
+/// 1` to the opposite orientation. The entry for `(u → v, v → w)` is 1
+/// unless the second half-edge is the reverse of the first. This is the
+/// adjacency matrix of the [oriented_line_graph](crate::oriented_line_graph).
+///
+/// # Example
+///
+/// ```rust
+/// use line_graph::hashimoto_matrix;
+/// use petgraph::graph::UnGraph;
+///
+/// let g = UnGraph::<(), ()>::from_edges([(0, 1), (1, 2), (2, 0)]);
+/// let b = hashimoto_matrix(&g);
+/// assert_eq!((b.rows(), b.cols()), (6, 6));
+/// // 0 → 1 → 2
+/// assert_eq!(b.get(0, 2), Some(&1));
+/// // 0 → 1 → 0
+/// assert_eq!(b.get(0, 1), None);
+/// ```
+pub fn hashimoto_matrix<N, E, Ix: IndexType>(
+    g: &UnGraph<N, E, Ix>,
+) -> CsrMatrix<usize> {
+    let outgoing = outgoing_half_edges(g);
+    let triplets = outgoing
+        .iter()
+        .flat_map(|out| non_backtracking_pairs(out).map(|(i, j)| (i, j, 1)));
+    let n = 2 * g.edge_count();
+    CsrMatrix::from_triplets(n, n, triplets)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        line_graph, oriented_line_graph, LineGraphBuilder, LoopPolicy,
+    };
+
+    fn adjacency_matrix<N, E>(g: &UnGraph<N, E>) -> CsrMatrix<usize> {
+        let triplets = g.edge_references().flat_map(|e| {
+            let (s, t) = (e.source().index(), e.target().index());
+            [(s, t, 1), (t, s, 1)]
+        });
+        CsrMatrix::from_triplets(g.node_count(), g.node_count(), triplets)
+    }
+
+    #[test]
+    fn csr() {
+        let m = CsrMatrix::from_triplets(
+            2,
+            3,
+            [(1, 2, 1), (0, 1, 2), (1, 0, 3), (1, 2, 4), (0, 0, 0)],
+        );
+        assert_eq!(m.indptr(), [0, 1, 3]);
+        assert_eq!(m.indices(), [1, 0, 2]);
+        assert_eq!(m.values(), [2, 3, 5]);
+        assert_eq!(m.get(1, 2), Some(&5));
+        assert_eq!(m.get(0, 0), None);
+        let t = m.transpose();
+        assert_eq!(t.transpose(), m);
+        assert_eq!(t.row(2).collect::<Vec<_>>(), [(1, &5)]);
+        assert_eq!(
+            &m + &m,
+            CsrMatrix::from_triplets(2, 3, [(0, 1, 4), (1, 0, 6), (1, 2, 10)])
+        );
+        let p = &m * &t;
+        assert_eq!(p, CsrMatrix::from_triplets(2, 2, [(0, 0, 4), (1, 1, 34)]));
+    }
+
+    #[test]
+    fn identity() {
+        let graphs = [
+            UnGraph::<(), ()>::from_edges([(0, 1), (1, 2), (2, 0), (2, 3)]),
+            UnGraph::from_edges([(0, 1), (0, 1), (1, 2), (1, 1), (2, 3)]),
+            UnGraph::from_edges([(0, 0), (0, 0), (0, 1), (2, 3), (3, 4)]),
+        ];
+        let builder = LineGraphBuilder::new().loops(LoopPolicy::Twice);
+        for g in graphs {
+            let b = incidence_matrix(&g);
+            let btb = &b.transpose() * &b;
+            let two = CsrMatrix::from_triplets(
+                g.edge_count(),
+                g.edge_count(),
+                (0..g.edge_count()).map(|i| (i, i, 2)),
+            );
+            let line = builder.build(&g);
+            let a = adjacency_matrix(&line);
+            assert_eq!(btb, &a + &two);
+            assert_eq!(line_graph_adjacency_matrix(&g), a);
+            if g.edge_references().all(|e| e.source() != e.target()) {
+                assert_eq!(adjacency_matrix(&line_graph(&g)), a);
+            }
+        }
+    }
+
+    #[test]
+    fn hashimoto() {
+        let g = UnGraph::<(), ()>::from_edges([
+            (0, 1),
+            (0, 1),
+            (1, 2),
+            (1, 1),
+            (2, 3),
+        ]);
+        let b = hashimoto_matrix(&g);
+        let oriented = oriented_line_graph(&g);
+        let triplets = oriented
+            .edge_references()
+            .map(|e| (e.source().index(), e.target().index(), 1));
+        let n = oriented.node_count();
+        assert_eq!(b, CsrMatrix::from_triplets(n, n, triplets));
+
+        // each half-edge continues along all but one of the half-edges
+        // starting at its head
+        let degree: usize = incidence_matrix(&g).row(1).map(|(_, v)| v).sum();
+        assert_eq!(degree, 5);
+        for d in [0, 2, 5, 6, 7] {
+            assert_eq!(b.row(d).count(), degree - 1);
+        }
+        assert_eq!(b.row(8).count(), 0);
+    }
+}
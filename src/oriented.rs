@@ -0,0 +1,139 @@
+use petgraph::{
+    graph::{DiGraph, IndexType, NodeIndex, UnGraph},
+    visit::EdgeRef,
+};
+
+/// Construct the oriented (non-backtracking) line graph of `g`
+///
+/// Each edge `e` of `g` is split into two half-edges, which become the
+/// nodes with indices `2 * e.index()`, oriented from the source to the
+/// target of `e`, and `2 * e.index() + 1`, oriented from the target to
+/// the source. Both nodes get the weight of `e`. For each pair of
+/// half-edges `u → v` and `v → w` there is an edge with the weight of
+/// `v`, unless the second half-edge is the reverse of the first. For
+/// simple graphs, this is the case exactly if `w = u`.
+///
+/// The adjacency matrix of the oriented line graph is the
+/// [hashimoto_matrix](crate::hashimoto_matrix). The oriented line graph
+/// has the same index type as `g`.
+///
+/// # Example
+///
+/// ```rust
+/// use line_graph::oriented_line_graph;
+/// use petgraph::graph::{NodeIndex, UnGraph};
+///
+/// let g = UnGraph::<(), ()>::from_edges([(0, 1), (1, 2)]);
+/// let oriented = oriented_line_graph(&g);
+/// assert_eq!(oriented.node_count(), 4);
+/// // 0 → 1 → 2 and 2 → 1 → 0
+/// assert_eq!(oriented.edge_count(), 2);
+/// assert!(oriented.contains_edge(NodeIndex::new(0), NodeIndex::new(2)));
+/// assert!(oriented.contains_edge(NodeIndex::new(3), NodeIndex::new(1)));
+/// ```
+pub fn oriented_line_graph<N, E, Ix>(g: &UnGraph<N, E, Ix>) -> DiGraph<E, N, Ix>
+where
+    N: Clone,
+    E: Clone,
+    Ix: IndexType,
+{
+    let outgoing = outgoing_half_edges(g);
+    let nedges = outgoing
+        .iter()
+        .map(|out| out.len() * out.len().saturating_sub(1))
+        .sum();
+    let mut res = DiGraph::with_capacity(2 * g.edge_count(), nedges);
+    for edge in g.edge_references() {
+        res.add_node(edge.weight().clone());
+        res.add_node(edge.weight().clone());
+    }
+    for (v, w) in g.node_weights().enumerate() {
+        for (d1, d2) in non_backtracking_pairs(&outgoing[v]) {
+            res.add_edge(NodeIndex::new(d1), NodeIndex::new(d2), w.clone());
+        }
+    }
+    res
+}
+
+// For each vertex, the indices of the half-edges starting there
+//
+// A self-loop contributes both of its half-edges.
+pub(crate) fn outgoing_half_edges<N, E, Ix: IndexType>(
+    g: &UnGraph<N, E, Ix>,
+) -> Vec<Vec<usize>> {
+    let mut outgoing = vec![Vec::new(); g.node_count()];
+    for e in g.edge_references() {
+        let d = 2 * e.id().index();
+        outgoing[e.source().index()].push(d);
+        outgoing[e.target().index()].push(d + 1);
+    }
+    outgoing
+}
+
+// All pairs of half-edges `(d1, d2)` passing through a vertex without
+// backtracking, given the half-edges `outgoing` starting there
+//
+// The half-edges arriving at the vertex are the reverses `d ^ 1` of
+// the outgoing ones.
+pub(crate) fn non_backtracking_pairs(
+    outgoing: &[usize],
+) -> impl Iterator<Item = (usize, usize)> + '_ {
+    outgoing.iter().flat_map(move |&d_in| {
+        outgoing
+            .iter()
+            .filter(move |&&d_out| d_out != d_in)
+            .map(move |&d_out| (d_in ^ 1, d_out))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::line_digraph;
+    use petgraph::algo::{is_isomorphic, tarjan_scc};
+
+    #[test]
+    fn cycle() {
+        let g = UnGraph::<(), ()>::from_edges([(0, 1), (1, 2), (2, 3), (3, 0)]);
+        let oriented = oriented_line_graph(&g);
+        assert_eq!(oriented.node_count(), 8);
+        assert_eq!(oriented.edge_count(), 8);
+        let mut sccs: Vec<_> = tarjan_scc(&oriented)
+            .into_iter()
+            .map(|mut c| {
+                c.sort();
+                c.into_iter().map(|n| n.index()).collect::<Vec<_>>()
+            })
+            .collect();
+        sccs.sort();
+        assert_eq!(sccs, [[0, 2, 4, 6], [1, 3, 5, 7]]);
+    }
+
+    #[test]
+    fn symmetric_digraph() {
+        // without the restriction, the oriented line graph is the
+        // directed line graph of the graph with both orientations
+        let g = UnGraph::<(), ()>::from_edges([
+            (0, 1),
+            (1, 2),
+            (2, 0),
+            (2, 3),
+            (3, 3),
+            (0, 1),
+        ]);
+        let mut both = DiGraph::<(), ()>::with_capacity(4, 12);
+        for _ in 0..4 {
+            both.add_node(());
+        }
+        for e in g.edge_references() {
+            both.add_edge(e.source(), e.target(), ());
+            both.add_edge(e.target(), e.source(), ());
+        }
+        let mut oriented = oriented_line_graph(&g);
+        for n in oriented.node_indices() {
+            let reverse = NodeIndex::new(n.index() ^ 1);
+            oriented.add_edge(n, reverse, ());
+        }
+        assert!(is_isomorphic(&oriented, &line_digraph(&both)));
+    }
+}
@@ -0,0 +1,113 @@
+use crate::{
+    add_line_nodes, allocate_line_graph, size::line_graph_size,
+    LineGraphEdgeRef, LoopPolicy,
+};
+
+use petgraph::{
+    graph::UnGraph,
+    visit::{
+        EdgeRef, GraphProp, IntoEdgeReferences, IntoNodeReferences,
+        NodeIndexable, NodeRef,
+    },
+    Undirected,
+};
+use rayon::prelude::*;
+
+/// Construct the line graph for `g` in parallel
+///
+/// The result is the same as for [line_graph](crate::line_graph),
+/// including the order of nodes and edges. The line graph edges of
+/// each vertex, including their weights, are computed in parallel and
+/// written to a preallocated buffer at offsets following the order of
+/// `g.node_references()`. Finally, the edges are moved from this
+/// buffer into the line graph.
+///
+/// # Example
+///
+/// ```rust
+/// use line_graph::{line_graph, par_line_graph};
+/// use petgraph::graph::UnGraph;
+///
+/// let g = UnGraph::<(), ()>::from_edges([(0, 1), (0, 2), (0, 3), (1, 2)]);
+/// let par = par_line_graph(&g);
+/// let seq = line_graph(&g);
+/// assert!(par.raw_edges().iter().map(|e| (e.source(), e.target()))
+///     .eq(seq.raw_edges().iter().map(|e| (e.source(), e.target()))));
+/// ```
+pub fn par_line_graph<G>(
+    g: G,
+) -> UnGraph<G::EdgeWeight, G::NodeWeight, <G::EdgeRef as LineGraphEdgeRef>::Ix>
+where
+    G: IntoNodeReferences
+        + IntoEdgeReferences
+        + NodeIndexable
+        + GraphProp<EdgeType = Undirected>,
+    G::EdgeRef: LineGraphEdgeRef,
+    G::NodeRef: Send,
+    <G::EdgeRef as LineGraphEdgeRef>::Ix: Send + Sync,
+    G::NodeWeight: Clone + Send,
+    G::EdgeWeight: Clone,
+{
+    let size = line_graph_size(g, LoopPolicy::default());
+    let mut line_graph = allocate_line_graph(size);
+    let (_, incident) = add_line_nodes(
+        g,
+        LoopPolicy::default(),
+        &mut line_graph,
+        |_| {},
+        |e| e.weight().clone(),
+    );
+
+    // Split the edge buffer into one exactly sized part per vertex
+    let mut line_edges = Vec::new();
+    line_edges.resize_with(size.edges, || None);
+    let mut parts = Vec::with_capacity(g.node_bound());
+    let mut rest = line_edges.as_mut_slice();
+    for node in g.node_references() {
+        let incident = &incident[NodeIndexable::to_index(&g, node.id())];
+        let k = incident.len();
+        let (part, tail) = rest.split_at_mut(k * k.saturating_sub(1) / 2);
+        parts.push((node, incident, part));
+        rest = tail;
+    }
+
+    parts.into_par_iter().for_each(|(node, incident, part)| {
+        let mut part = part.iter_mut();
+        for (s, &v1) in incident.iter().enumerate() {
+            for &v2 in &incident[s + 1..] {
+                *part.next().unwrap() = Some((v1, v2, node.weight().clone()));
+            }
+        }
+    });
+
+    for (v1, v2, weight) in line_edges.into_iter().flatten() {
+        line_graph.add_edge(v1, v2, weight);
+    }
+    line_graph
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{line_graph, test_util::random_multigraph};
+
+    #[test]
+    fn sequential() {
+        for seed in 0..20 {
+            let g = random_multigraph(50, 20 * seed as usize, seed)
+                .map(|n, _| n.index() as u32, |e, _| e.index());
+            let par = par_line_graph(&g);
+            let seq = line_graph(&g);
+            assert_eq!(
+                par.node_weights().collect::<Vec<_>>(),
+                seq.node_weights().collect::<Vec<_>>()
+            );
+            let edges = |g: &UnGraph<usize, u32>| {
+                g.edge_references()
+                    .map(|e| (e.source(), e.target(), *e.weight()))
+                    .collect::<Vec<_>>()
+            };
+            assert_eq!(edges(&par), edges(&seq));
+        }
+    }
+}
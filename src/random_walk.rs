@@ -0,0 +1,206 @@
+use crate::{
+    build_line_graph, input::edge_references, size::incidences,
+    LineGraphEdgeRef, LoopPolicy,
+};
+
+use petgraph::{
+    graph::UnGraph,
+    visit::{
+        EdgeIndexable, EdgeRef, GraphProp, IntoEdgeReferences,
+        IntoNodeReferences, NodeIndexable, NodeRef,
+    },
+    Undirected,
+};
+
+/// Construct the line graph for `g` normalised for random walks
+///
+/// This is the line graph `C(G)` of [Evans and
+/// Lambiotte](https://doi.org/10.1103/PhysRevE.80.016105). Each line
+/// graph edge between two edges sharing the vertex `v` has weight `1 /
+/// (k(v) - 1)`, where `k(v)` is the number of edges incident to `v`.
+/// Thus, the edges of each line graph node have a total weight of 1 for
+/// each vertex of the original edge with further incident edges, and a
+/// random walk on the line graph that follows edges with a probability
+/// proportional to their weights corresponds to a random walk on the
+/// edges of `g`.
+///
+/// As in [line_graph](crate::line_graph), each line graph node has
+/// the weight of the corresponding original edge, and a self-loop is
+/// incident to its vertex once.
+///
+/// # Example
+///
+/// ```rust
+/// use line_graph::random_walk_line_graph;
+/// use petgraph::graph::UnGraph;
+///
+/// let g = UnGraph::<(), ()>::from_edges([(0, 1), (0, 2), (0, 3)]);
+/// let g_line = random_walk_line_graph(&g);
+/// assert_eq!(g_line.edge_count(), 3);
+/// assert!(g_line.edge_weights().all(|&w| w == 0.5));
+/// ```
+pub fn random_walk_line_graph<G>(
+    g: G,
+) -> UnGraph<G::EdgeWeight, f64, <G::EdgeRef as LineGraphEdgeRef>::Ix>
+where
+    G: IntoNodeReferences
+        + IntoEdgeReferences
+        + NodeIndexable
+        + GraphProp<EdgeType = Undirected>,
+    G::EdgeRef: LineGraphEdgeRef,
+    G::EdgeWeight: Clone,
+{
+    let degrees = incidences(g, LoopPolicy::default());
+    build_line_graph(
+        g,
+        LoopPolicy::default(),
+        |e| e.weight().clone(),
+        |_, _, v| {
+            let k = degrees[NodeIndexable::to_index(&g, v.id())];
+            1. / (k - 1) as f64
+        },
+    )
+}
+
+/// Construct the line graph for `g` normalised for weighted random
+/// walks
+///
+/// This is the line graph `E(G)` of [Evans and
+/// Lambiotte](https://doi.org/10.1103/PhysRevE.80.016105) with the
+/// original edge weights given by `weight_fn`. Each line graph edge
+/// between two edges with weights `w1` and `w2` sharing the vertex `v`
+/// has weight `w1 * w2 / s(v)`, where the strength `s(v)` is the sum of
+/// the weights of all edges incident to `v`. If `s(v)` is zero, the
+/// weight is zero as well.
+///
+/// `weight_fn` is called once for each original edge. As in
+/// [random_walk_line_graph], each line graph node has the weight of
+/// the corresponding original edge, and a self-loop is incident to its
+/// vertex once.
+///
+/// # Example
+///
+/// ```rust
+/// use line_graph::weighted_random_walk_line_graph;
+/// use petgraph::graph::UnGraph;
+///
+/// let g = UnGraph::<(), f64>::from_edges([(0, 1, 1.), (1, 2, 3.)]);
+/// let g_line = weighted_random_walk_line_graph(&g, |e| *e.weight());
+/// assert_eq!(g_line.edge_weights().next(), Some(&0.75));
+/// ```
+pub fn weighted_random_walk_line_graph<G, F>(
+    g: G,
+    mut weight_fn: F,
+) -> UnGraph<G::EdgeWeight, f64, <G::EdgeRef as LineGraphEdgeRef>::Ix>
+where
+    G: IntoNodeReferences
+        + IntoEdgeReferences
+        + NodeIndexable
+        + EdgeIndexable
+        + GraphProp<EdgeType = Undirected>,
+    G::EdgeRef: LineGraphEdgeRef,
+    G::EdgeWeight: Clone,
+    F: FnMut(G::EdgeRef) -> f64,
+{
+    let mut weights = vec![0.; g.edge_bound()];
+    let mut strengths = vec![0.; g.node_bound()];
+    for e in edge_references(g) {
+        let w = weight_fn(e);
+        weights[EdgeIndexable::to_index(&g, e.id())] = w;
+        let s = NodeIndexable::to_index(&g, e.source());
+        let t = NodeIndexable::to_index(&g, e.target());
+        strengths[s] += w;
+        if s != t {
+            strengths[t] += w;
+        }
+    }
+    build_line_graph(
+        g,
+        LoopPolicy::default(),
+        |e| e.weight().clone(),
+        |e1, e2, v| {
+            let w1 = weights[EdgeIndexable::to_index(&g, e1.id())];
+            let w2 = weights[EdgeIndexable::to_index(&g, e2.id())];
+            let s = strengths[NodeIndexable::to_index(&g, v.id())];
+            if s == 0. {
+                0.
+            } else {
+                w1 * w2 / s
+            }
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use petgraph::graph::NodeIndex;
+
+    fn strength(g: &UnGraph<f64, f64>, n: NodeIndex) -> f64 {
+        g.edges(n).map(|e| *e.weight()).sum()
+    }
+
+    #[test]
+    fn unweighted() {
+        let g = UnGraph::<(), f64>::from_edges([
+            (0, 1, 1.),
+            (0, 2, 1.),
+            (0, 3, 1.),
+            (1, 2, 1.),
+            (3, 4, 1.),
+            (3, 3, 1.),
+        ]);
+        let g_line = random_walk_line_graph(&g);
+        assert_eq!(g_line.edge_count(), 3 + 1 + 1 + 3);
+        let strengths: Vec<_> = g_line
+            .node_indices()
+            .map(|n| strength(&g_line, n))
+            .collect();
+        // one per endpoint with further edges; the loop at 3 only
+        // counts once
+        assert_eq!(strengths, [2., 2., 2., 2., 1., 1.]);
+
+        // with unit weights, the strength is the degree `k` and the
+        // weights are `1 / k` instead of `1 / (k - 1)`
+        let weighted = weighted_random_walk_line_graph(&g, |e| *e.weight());
+        assert_eq!(weighted.edge_count(), g_line.edge_count());
+        for (w, w_line) in weighted.edge_weights().zip(g_line.edge_weights()) {
+            assert!((1. / w - 1. / w_line - 1.).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn weighted() {
+        let g = UnGraph::<(), f64>::from_edges([
+            (0, 1, 1.),
+            (0, 2, 2.),
+            (0, 3, 4.),
+            (1, 2, 0.5),
+        ]);
+        let g_line = weighted_random_walk_line_graph(&g, |e| *e.weight());
+        // the strength of a line graph node is the sum of
+        // `w (s - w) / s` over both vertices of the original edge
+        let s: Vec<f64> = g
+            .node_indices()
+            .map(|v| g.edges(v).map(|e| e.weight()).sum())
+            .collect();
+        for e in g.edge_references() {
+            let w = *e.weight();
+            let expected: f64 = [e.source(), e.target()]
+                .iter()
+                .map(|v| w * (s[v.index()] - w) / s[v.index()])
+                .sum();
+            let actual = strength(&g_line, NodeIndex::new(e.id().index()));
+            assert!((actual - expected).abs() < 1e-12);
+        }
+        let e = g_line.find_edge(0.into(), 1.into()).unwrap();
+        assert_eq!(g_line[e], 2. / 7.);
+    }
+
+    #[test]
+    fn zero_strength() {
+        let g = UnGraph::<(), f64>::from_edges([(0, 1, 0.), (1, 2, 0.)]);
+        let g_line = weighted_random_walk_line_graph(&g, |e| *e.weight());
+        assert_eq!(g_line.edge_weights().collect::<Vec<_>>(), [&0.]);
+    }
+}
@@ -0,0 +1,431 @@
+use crate::root::{root_edges, RootError};
+
+use petgraph::{
+    algo::{is_isomorphic, subgraph_isomorphisms_iter},
+    graph::{DefaultIx, IndexType, NodeIndex, UnGraph},
+};
+use std::{error::Error, fmt};
+
+/// Beineke's nine forbidden induced subgraphs
+///
+/// A simple graph is a line graph if and only if it does not contain
+/// any of these graphs as an induced subgraph. The numbering may differ
+/// from the one used in other references.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum BeinekeGraph {
+    /// The claw `K_{1,3}`
+    G1,
+    /// The complete bipartite graph `K_{2,3}` with an additional edge
+    /// in the part of size three
+    G2,
+    /// The complete graph `K_5` without one edge
+    G3,
+    /// 6 vertices, 7 edges
+    G4,
+    /// 6 vertices, 9 edges
+    G5,
+    /// 6 vertices, 9 edges
+    G6,
+    /// 6 vertices, 8 edges
+    G7,
+    /// 6 vertices, 11 edges
+    G8,
+    /// 6 vertices, 10 edges
+    G9,
+}
+
+impl BeinekeGraph {
+    /// All forbidden subgraphs
+    pub const ALL: [BeinekeGraph; 9] = [
+        BeinekeGraph::G1,
+        BeinekeGraph::G2,
+        BeinekeGraph::G3,
+        BeinekeGraph::G4,
+        BeinekeGraph::G5,
+        BeinekeGraph::G6,
+        BeinekeGraph::G7,
+        BeinekeGraph::G8,
+        BeinekeGraph::G9,
+    ];
+
+    /// The edges of the graph
+    pub fn edges(&self) -> &'static [(u32, u32)] {
+        use BeinekeGraph::*;
+        match self {
+            G1 => &[(0, 1), (0, 2), (0, 3)],
+            G2 => &[(0, 2), (0, 3), (0, 4), (1, 2), (1, 3), (1, 4), (2, 3)],
+            G3 => &[
+                (0, 1),
+                (0, 2),
+                (0, 3),
+                (0, 4),
+                (1, 2),
+                (1, 3),
+                (1, 4),
+                (2, 3),
+                (2, 4),
+            ],
+            G4 => &[(0, 2), (0, 3), (0, 5), (1, 2), (1, 3), (1, 4), (2, 3)],
+            G5 => &[
+                (0, 1),
+                (0, 2),
+                (0, 5),
+                (1, 2),
+                (1, 3),
+                (1, 4),
+                (2, 3),
+                (2, 4),
+                (3, 4),
+            ],
+            G6 => &[
+                (0, 1),
+                (0, 2),
+                (0, 3),
+                (0, 5),
+                (1, 2),
+                (1, 5),
+                (2, 3),
+                (2, 4),
+                (3, 4),
+            ],
+            G7 => &[
+                (0, 3),
+                (0, 4),
+                (0, 5),
+                (1, 2),
+                (1, 5),
+                (2, 3),
+                (2, 4),
+                (3, 4),
+            ],
+            G8 => &[
+                (0, 1),
+                (0, 2),
+                (0, 3),
+                (0, 4),
+                (0, 5),
+                (1, 2),
+                (1, 3),
+                (1, 4),
+                (1, 5),
+                (2, 5),
+                (3, 4),
+            ],
+            G9 => &[
+                (0, 1),
+                (0, 2),
+                (0, 3),
+                (0, 4),
+                (0, 5),
+                (1, 4),
+                (1, 5),
+                (2, 3),
+                (2, 5),
+                (3, 4),
+            ],
+        }
+    }
+
+    /// Construct the graph
+    pub fn graph(&self) -> UnGraph<(), ()> {
+        UnGraph::from_edges(self.edges())
+    }
+}
+
+/// Reason why a graph is not a line graph
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum NotLineGraph<Ix = DefaultIx> {
+    /// There is a self-loop at the given node
+    Loop(NodeIndex<Ix>),
+    /// There is more than one edge between the given nodes
+    ParallelEdges(NodeIndex<Ix>, NodeIndex<Ix>),
+    /// The given nodes induce a forbidden subgraph
+    ///
+    /// The `i`th node corresponds to vertex `i` in
+    /// [BeinekeGraph::graph].
+    Forbidden(BeinekeGraph, Vec<NodeIndex<Ix>>),
+}
+
+impl<Ix: IndexType> fmt::Display for NotLineGraph<Ix> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Loop(n) => write!(f, "self-loop at node {}", n.index()),
+            Self::ParallelEdges(a, b) => write!(
+                f,
+                "parallel edges between nodes {} and {}",
+                a.index(),
+                b.index()
+            ),
+            Self::Forbidden(kind, nodes) => {
+                write!(f, "nodes")?;
+                for (i, n) in nodes.iter().enumerate() {
+                    let sep = if i == 0 { " " } else { ", " };
+                    write!(f, "{sep}{}", n.index())?;
+                }
+                write!(f, " induce the forbidden subgraph {kind:?}")
+            }
+        }
+    }
+}
+
+impl<Ix: IndexType> Error for NotLineGraph<Ix> {}
+
+/// Check whether `h` is the line graph of a simple graph
+///
+/// If `h` is not a line graph, the returned error explains why. In
+/// particular, if `h` is simple, it contains one of
+/// [Beineke's](https://en.wikipedia.org/wiki/Line_graph#Characterization_and_recognition)
+/// nine forbidden induced subgraphs and the error lists its nodes.
+///
+/// Recognising a line graph takes linear time, see
+/// [root_graph](crate::root_graph). Finding the forbidden subgraph
+/// takes time `O(m log n)`, where `n` and `m` are the numbers of nodes
+/// and edges within distance four of the node at which the recognition
+/// failed.
+///
+/// # Example
+///
+/// ```rust
+/// use line_graph::{is_line_graph, BeinekeGraph, NotLineGraph};
+/// use petgraph::graph::UnGraph;
+///
+/// let g = UnGraph::<(), ()>::from_edges([(0, 1), (1, 2), (1, 3), (3, 4)]);
+/// let Err(NotLineGraph::Forbidden(kind, nodes)) = is_line_graph(&g) else {
+///     panic!("not a line graph");
+/// };
+/// assert_eq!(kind, BeinekeGraph::G1);
+/// assert_eq!(nodes[0].index(), 1);
+/// ```
+pub fn is_line_graph<N, E, Ix: IndexType>(
+    h: &UnGraph<N, E, Ix>,
+) -> Result<(), NotLineGraph<Ix>> {
+    let (processed, node) = match root_edges(h) {
+        Ok(_) => return Ok(()),
+        Err(RootError::Loop(n)) => {
+            return Err(NotLineGraph::Loop(NodeIndex::new(n)))
+        }
+        Err(RootError::ParallelEdges(a, b)) => {
+            return Err(NotLineGraph::ParallelEdges(
+                NodeIndex::new(a),
+                NodeIndex::new(b),
+            ))
+        }
+        Err(RootError::NotLineGraph { processed, node }) => (processed, node),
+    };
+
+    // Any forbidden subgraph of the nodes processed so far contains
+    // `node`. Since all forbidden subgraphs have diameter at most four,
+    // it is enough to consider the nodes within that distance.
+    let mut allowed = vec![false; h.node_count()];
+    for n in processed {
+        allowed[n] = true;
+    }
+    let mut nodes = vec![node];
+    let mut in_nodes = vec![false; h.node_count()];
+    in_nodes[node] = true;
+    let mut start = 0;
+    for _ in 0..4 {
+        let end = nodes.len();
+        for i in start..end {
+            for n in h.neighbors(NodeIndex::new(nodes[i])) {
+                let n = n.index();
+                if allowed[n] && !in_nodes[n] {
+                    in_nodes[n] = true;
+                    nodes.push(n);
+                }
+            }
+        }
+        start = end;
+    }
+
+    // Removing nodes from a line graph gives a line graph, so we end
+    // up with a minimal forbidden subgraph. We try to remove chunks of
+    // nodes from the end, halving the chunk size until we find a node
+    // we have to keep. Since we keep at most six nodes, this takes a
+    // logarithmic number of steps.
+    let mut pos = vec![usize::MAX; h.node_count()];
+    let mut keep = Vec::new();
+    let mut chunk = nodes.len();
+    while let Some(&last) = nodes.last() {
+        let split = nodes.len() - chunk.min(nodes.len());
+        let rest: Vec<_> =
+            keep.iter().chain(&nodes[..split]).copied().collect();
+        if root_edges(&induced_subgraph(h, &rest, &mut pos)).is_err() {
+            nodes.truncate(split);
+        } else if chunk > 1 {
+            chunk = chunk.div_ceil(2);
+        } else {
+            keep.push(last);
+            nodes.pop();
+            chunk = nodes.len();
+        }
+    }
+
+    let sub = induced_subgraph(h, &keep, &mut pos);
+    for kind in BeinekeGraph::ALL {
+        let forbidden = kind.graph();
+        if !is_isomorphic(&forbidden, &sub) {
+            continue;
+        }
+        let (g0, g1) = (&forbidden, &sub);
+        let (mut node_match, mut edge_match) =
+            (|_: &_, _: &_| true, |_: &_, _: &_| true);
+        let iso = subgraph_isomorphisms_iter(
+            &g0,
+            &g1,
+            &mut node_match,
+            &mut edge_match,
+        )
+        .and_then(|mut iso| iso.next())
+        .unwrap();
+        let nodes = iso.into_iter().map(|i| NodeIndex::new(keep[i]));
+        return Err(NotLineGraph::Forbidden(kind, nodes.collect()));
+    }
+    unreachable!("Minimal non-line graph is not one of Beineke's graphs")
+}
+
+/// Subgraph of `h` induced by `nodes`
+///
+/// `pos` has to be of length `h.node_count()` with all entries equal to
+/// `usize::MAX`. It is restored before returning.
+fn induced_subgraph<N, E, Ix: IndexType>(
+    h: &UnGraph<N, E, Ix>,
+    nodes: &[usize],
+    pos: &mut [usize],
+) -> UnGraph<(), ()> {
+    let mut sub = UnGraph::with_capacity(nodes.len(), 0);
+    for (i, &n) in nodes.iter().enumerate() {
+        pos[n] = i;
+        sub.add_node(());
+    }
+    for &n in nodes {
+        for m in h.neighbors(NodeIndex::new(n)) {
+            let m = pos[m.index()];
+            if m != usize::MAX && pos[n] < m {
+                sub.add_edge(NodeIndex::new(pos[n]), NodeIndex::new(m), ());
+            }
+        }
+    }
+    for &n in nodes {
+        pos[n] = usize::MAX;
+    }
+    sub
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{line_graph, test_util::random_graph};
+
+    fn assert_induces(
+        g: &UnGraph<(), ()>,
+        kind: BeinekeGraph,
+        nodes: &[NodeIndex],
+    ) {
+        let sub = kind.graph();
+        assert_eq!(sub.node_count(), nodes.len());
+        for a in sub.node_indices() {
+            for b in sub.node_indices() {
+                assert_eq!(
+                    sub.contains_edge(a, b),
+                    g.contains_edge(nodes[a.index()], nodes[b.index()])
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn line_graphs() {
+        let g = UnGraph::<(), ()>::from_edges([
+            (0, 1),
+            (0, 2),
+            (0, 3),
+            (1, 4),
+            (2, 3),
+            (3, 4),
+        ]);
+        assert_eq!(is_line_graph(&line_graph(&g)), Ok(()));
+        let claw = BeinekeGraph::G1.graph();
+        assert_eq!(is_line_graph(&line_graph(&claw)), Ok(()));
+    }
+
+    #[test]
+    fn forbidden() {
+        for kind in BeinekeGraph::ALL {
+            let g = kind.graph();
+            let Err(NotLineGraph::Forbidden(found, nodes)) = is_line_graph(&g)
+            else {
+                panic!("{kind:?} is not a line graph");
+            };
+            assert_eq!(found, kind);
+            assert_eq!(nodes.len(), g.node_count());
+            for &(a, b) in kind.edges() {
+                let (a, b) = (nodes[a as usize], nodes[b as usize]);
+                assert!(g.contains_edge(a, b));
+            }
+        }
+    }
+
+    #[test]
+    fn embedded() {
+        // line graph of a triangle with pendant edges, plus a claw
+        let mut g = line_graph(&UnGraph::<(), ()>::from_edges([
+            (0, 1),
+            (1, 2),
+            (2, 0),
+            (0, 3),
+            (1, 4),
+            (2, 5),
+        ]));
+        let centre = NodeIndex::new(3);
+        for _ in 0..2 {
+            let n = g.add_node(());
+            g.add_edge(centre, n, ());
+        }
+        let Err(NotLineGraph::Forbidden(kind, nodes)) = is_line_graph(&g)
+        else {
+            panic!("not a line graph");
+        };
+        assert_induces(&g, kind, &nodes);
+    }
+
+    #[test]
+    fn large() {
+        // line graph of K_40 with a pendant node
+        let mut g = line_graph(&UnGraph::<(), ()>::from_edges(
+            (0..40).flat_map(|a| (0..a).map(move |b| (a, b))),
+        ));
+        let n = g.add_node(());
+        g.add_edge(NodeIndex::new(0), n, ());
+        let Err(NotLineGraph::Forbidden(kind, nodes)) = is_line_graph(&g)
+        else {
+            panic!("not a line graph");
+        };
+        assert_induces(&g, kind, &nodes);
+    }
+
+    #[test]
+    fn random() {
+        for seed in 0..200 {
+            let g = random_graph(10, (seed % 20) as usize, seed);
+            match is_line_graph(&g) {
+                Ok(()) => assert!(crate::root_graph::<(), (), _>(&g).is_some()),
+                Err(NotLineGraph::Forbidden(kind, nodes)) => {
+                    assert_induces(&g, kind, &nodes)
+                }
+                Err(err) => panic!("{err:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn multigraphs() {
+        let g = UnGraph::<(), ()>::from_edges([(0, 1), (1, 1)]);
+        assert_eq!(is_line_graph(&g), Err(NotLineGraph::Loop(1.into())));
+        let g = UnGraph::<(), ()>::from_edges([(0, 1), (0, 1)]);
+        assert!(matches!(
+            is_line_graph(&g),
+            Err(NotLineGraph::ParallelEdges(_, _))
+        ));
+    }
+}
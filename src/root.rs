@@ -0,0 +1,412 @@
+use crate::LineGraphMap;
+
+use petgraph::graph::{EdgeIndex, IndexType, NodeIndex, UnGraph};
+
+/// Reconstruct a graph whose line graph is `h`
+///
+/// If `h` is the line graph of a simple graph `g`, returns a graph
+/// isomorphic to `g` without isolated vertices, together with the
+/// correspondence between `h` and the returned graph. Otherwise,
+/// returns `None`. In particular, `None` is returned if `h` has
+/// self-loops or parallel edges.
+///
+/// Each node `n` of `h` becomes the edge with index `n.index()` in the
+/// returned graph, with the same weight. Each vertex of the returned
+/// graph gets the weight of one of the edges of `h` it corresponds to,
+/// or the default weight if there is no such edge.
+///
+/// By Whitney's theorem, the root graph of a connected line graph is
+/// unique up to isomorphism, except for the triangle, which is both
+/// the line graph of itself and of the star with three edges. In this
+/// case, the triangle is returned.
+///
+/// The algorithm adds the nodes of `h` one at a time in breadth-first
+/// order and extends the root graph of the nodes added so far, in the
+/// spirit of Degiorgi and Simon. Apart from a bounded number of
+/// alternative root graphs that are tracked for small components, the
+/// running time is linear in the size of `h`.
+///
+/// # Example
+///
+/// ```rust
+/// use line_graph::{line_graph, root_graph};
+/// use petgraph::{algo::is_isomorphic, graph::UnGraph};
+///
+/// let g = UnGraph::<(), ()>::from_edges([(0, 1), (1, 2), (2, 3), (3, 1)]);
+/// let (root, map) = root_graph(&line_graph(&g)).unwrap();
+/// assert!(is_isomorphic(&g, &root));
+///
+/// // the claw is not a line graph
+/// let claw = UnGraph::<(), ()>::from_edges([(0, 1), (0, 2), (0, 3)]);
+/// assert!(root_graph(&claw).is_none());
+/// ```
+#[allow(clippy::type_complexity)]
+pub fn root_graph<N, E, Ix>(
+    h: &UnGraph<E, N, Ix>,
+) -> Option<(UnGraph<N, E>, LineGraphMap<NodeIndex, EdgeIndex, Ix>)>
+where
+    N: Clone + Default,
+    E: Clone,
+    Ix: IndexType,
+{
+    let ends = root_edges(h).ok()?;
+    let nvertices = ends.iter().map(|&(s, t)| s.max(t) + 1).max().unwrap_or(0);
+
+    let mut vertex_weights = vec![None; nvertices];
+    let mut map = LineGraphMap::with_capacity(h.node_count(), h.edge_count());
+    for e in h.raw_edges() {
+        let v =
+            shared_vertex(ends[e.source().index()], ends[e.target().index()]);
+        map.add_edge(NodeIndex::new(v));
+        vertex_weights[v].get_or_insert_with(|| e.weight.clone());
+    }
+
+    let mut root = UnGraph::with_capacity(nvertices, h.node_count());
+    for w in vertex_weights {
+        root.add_node(w.unwrap_or_default());
+    }
+    for (n, &(s, t)) in ends.iter().enumerate() {
+        let (s, t) = (NodeIndex::new(s), NodeIndex::new(t));
+        let e = root.add_edge(s, t, h[NodeIndex::new(n)].clone());
+        map.add_node(e, e.index(), s, t);
+    }
+    Some((root, map))
+}
+
+fn shared_vertex(e1: (usize, usize), e2: (usize, usize)) -> usize {
+    if e1.0 == e2.0 || e1.0 == e2.1 {
+        e1.0
+    } else {
+        debug_assert!(e1.1 == e2.0 || e1.1 == e2.1);
+        e1.1
+    }
+}
+
+/// Failure to extend a partial root graph
+///
+/// `processed` are the nodes of `h` that could be assigned root edges
+/// before adding `node` failed. The subgraph of `h` induced by
+/// `processed` is a line graph, but adding `node` to it gives a graph
+/// that is not.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) enum RootError {
+    Loop(usize),
+    ParallelEdges(usize, usize),
+    NotLineGraph { processed: Vec<usize>, node: usize },
+}
+
+/// Find the endpoints of the root graph edge for each node of `h`
+pub(crate) fn root_edges<E, N, Ix: IndexType>(
+    h: &UnGraph<E, N, Ix>,
+) -> Result<Vec<(usize, usize)>, RootError> {
+    if let Some(e) = h.raw_edges().iter().find(|e| e.source() == e.target()) {
+        return Err(RootError::Loop(e.source().index()));
+    }
+
+    let n = h.node_count();
+    let mut ends = vec![(usize::MAX, usize::MAX); n];
+    let mut nvertices = 0;
+    let mut seen = vec![false; n];
+    let mut pos = vec![usize::MAX; n];
+    let mut marked = vec![false; n];
+    for start in 0..n {
+        if seen[start] {
+            continue;
+        }
+        // breadth-first order of the component containing `start`
+        let mut order = vec![start];
+        seen[start] = true;
+        let mut next = 0;
+        while next < order.len() {
+            let x = order[next];
+            next += 1;
+            for y in h.neighbors(NodeIndex::new(x)) {
+                if !seen[y.index()] {
+                    seen[y.index()] = true;
+                    order.push(y.index());
+                }
+            }
+        }
+        for (i, &x) in order.iter().enumerate() {
+            pos[x] = i;
+        }
+
+        let mut partials = vec![Partial::new()];
+        let mut processed = Vec::with_capacity(order.len());
+        for (k, &y) in order.iter().enumerate().skip(1) {
+            processed.clear();
+            for x in h.neighbors(NodeIndex::new(y)) {
+                let i = pos[x.index()];
+                if i > k {
+                    continue;
+                }
+                if marked[i] {
+                    return Err(RootError::ParallelEdges(y, x.index()));
+                }
+                marked[i] = true;
+                processed.push(i);
+            }
+            processed.sort_unstable();
+            let mut extended = Vec::new();
+            for (i, partial) in partials.iter().enumerate() {
+                for (p, q) in partial.extensions(&processed, &marked) {
+                    extended.push((i, p, q));
+                }
+            }
+            for &i in &processed {
+                marked[i] = false;
+            }
+            match extended.as_slice() {
+                [] => {
+                    return Err(RootError::NotLineGraph {
+                        processed: order[..k].to_vec(),
+                        node: y,
+                    })
+                }
+                &[(i, p, q)] => {
+                    partials.swap(0, i);
+                    partials.truncate(1);
+                    partials[0].push(p, q);
+                }
+                _ => {
+                    let mut new: Vec<Partial> = Vec::new();
+                    let mut keys = Vec::new();
+                    for (i, p, q) in extended {
+                        let mut partial = partials[i].clone();
+                        partial.push(p, q);
+                        let key = partial.canonical();
+                        if !keys.contains(&key) {
+                            keys.push(key);
+                            new.push(partial);
+                        }
+                    }
+                    partials = new;
+                }
+            }
+        }
+        // prefer the triangle over the claw as root of the triangle
+        let partial = partials
+            .into_iter()
+            .min_by_key(|p| p.incident.len())
+            .unwrap();
+        for (x, (s, t)) in order.into_iter().zip(partial.ends) {
+            ends[x] = (nvertices + s, nvertices + t);
+        }
+        nvertices += partial.incident.len();
+    }
+    Ok(ends)
+}
+
+/// Root graph for the first nodes of a component in breadth-first order
+#[derive(Clone, Debug)]
+struct Partial {
+    /// Endpoints of the root edge for each node
+    ends: Vec<(usize, usize)>,
+    /// Nodes with root edges incident to each root vertex
+    incident: Vec<Vec<usize>>,
+}
+
+const NEW: usize = usize::MAX;
+
+impl Partial {
+    fn new() -> Self {
+        Self {
+            ends: vec![(0, 1)],
+            incident: vec![vec![0], vec![0]],
+        }
+    }
+
+    /// Possible root edges `(p, q)` for a new node adjacent to exactly
+    /// the `processed` nodes, which must be marked
+    ///
+    /// `q == NEW` denotes a vertex that is not part of the partial root
+    /// graph yet.
+    fn extensions(
+        &self,
+        processed: &[usize],
+        marked: &[bool],
+    ) -> Vec<(usize, usize)> {
+        let mut res = Vec::new();
+        let (a, b) = self.ends[processed[0]];
+        for p in [a, b] {
+            let other = processed.iter().find(|&&x| {
+                let (c, d) = self.ends[x];
+                c != p && d != p
+            });
+            let candidates = match other {
+                Some(&x) => {
+                    let (c, d) = self.ends[x];
+                    [c, d]
+                }
+                None => [NEW, NEW],
+            };
+            for q in candidates {
+                if self.is_extension(p, q, processed, marked)
+                    && !res.contains(&(p, q))
+                {
+                    res.push((p, q));
+                }
+            }
+        }
+        res
+    }
+
+    fn is_extension(
+        &self,
+        p: usize,
+        q: usize,
+        processed: &[usize],
+        marked: &[bool],
+    ) -> bool {
+        let at_p = &self.incident[p];
+        let at_q: &[usize] = if q == NEW { &[] } else { &self.incident[q] };
+        at_p.len() + at_q.len() == processed.len()
+            && at_p.iter().chain(at_q).all(|&x| marked[x])
+            && at_p.iter().all(|&x| {
+                let (c, d) = self.ends[x];
+                c != q && d != q
+            })
+    }
+
+    fn push(&mut self, p: usize, q: usize) {
+        let x = self.ends.len();
+        let q = if q == NEW {
+            self.incident.push(Vec::new());
+            self.incident.len() - 1
+        } else {
+            q
+        };
+        self.ends.push((p, q));
+        self.incident[p].push(x);
+        self.incident[q].push(x);
+    }
+
+    /// Representation that is independent of the labels of the root vertices
+    fn canonical(&self) -> Vec<(usize, usize)> {
+        let relabel = |first: (usize, usize)| {
+            let mut label = vec![NEW; self.incident.len()];
+            label[first.0] = 0;
+            label[first.1] = 1;
+            let mut next = 2;
+            self.ends
+                .iter()
+                .map(|&(s, t)| {
+                    for v in [s, t] {
+                        if label[v] == NEW {
+                            label[v] = next;
+                            next += 1;
+                        }
+                    }
+                    (label[s].min(label[t]), label[s].max(label[t]))
+                })
+                .collect::<Vec<_>>()
+        };
+        let (s, t) = self.ends[0];
+        relabel((s, t)).min(relabel((t, s)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{line_graph, test_util::random_graph, LineGraphBuilder};
+    use petgraph::algo::is_isomorphic;
+
+    fn assert_round_trip(g: &UnGraph<(), ()>) {
+        let h = line_graph(g);
+        let (root, map) = root_graph(&h).unwrap();
+        assert!(is_isomorphic(&h, &line_graph(&root)));
+        for n in h.node_indices() {
+            let e = map.edge(n).unwrap();
+            assert_eq!(map.endpoints(n), root.edge_endpoints(e));
+        }
+        for e in h.edge_indices() {
+            let (a, b) = h.edge_endpoints(e).unwrap();
+            let v = map.vertex(e).unwrap();
+            for n in [a, b] {
+                let (s, t) = map.endpoints(n).unwrap();
+                assert!(s == v || t == v);
+            }
+        }
+    }
+
+    #[test]
+    fn small() {
+        let graphs = [
+            vec![(0, 1)],
+            vec![(0, 1), (1, 2)],
+            vec![(0, 1), (1, 2), (2, 0)],
+            vec![(0, 1), (0, 2), (0, 3)],
+            vec![(0, 1), (0, 2), (0, 3), (1, 2)],
+            vec![(0, 1), (0, 2), (0, 3), (1, 2), (1, 3)],
+            vec![(0, 1), (0, 2), (0, 3), (1, 2), (1, 3), (2, 3)],
+            vec![(0, 1), (0, 2), (0, 3), (0, 4), (0, 5)],
+            vec![(0, 1), (2, 3), (3, 4), (4, 2), (5, 6), (5, 7), (5, 8)],
+        ];
+        for edges in graphs {
+            let g = UnGraph::from_edges(edges);
+            assert_round_trip(&g);
+        }
+    }
+
+    #[test]
+    fn random() {
+        for seed in 0..200 {
+            let g = random_graph(12, 10 + (seed % 25) as usize, seed);
+            assert_round_trip(&g);
+        }
+    }
+
+    #[test]
+    fn unique_root() {
+        // connected, no isolated vertices, not the claw
+        let g = UnGraph::<(), ()>::from_edges([
+            (0, 1),
+            (0, 2),
+            (0, 3),
+            (1, 4),
+            (2, 3),
+            (3, 4),
+        ]);
+        let (root, _) = root_graph(&line_graph(&g)).unwrap();
+        assert!(is_isomorphic(&g, &root));
+    }
+
+    #[test]
+    fn index_type() {
+        let g = UnGraph::<(), ()>::from_edges([(0, 1), (1, 2), (2, 0), (2, 3)]);
+        let h = LineGraphBuilder::new().index_type::<u16>().build(&g);
+        let (root, map) = root_graph(&h).unwrap();
+        for e in h.edge_indices() {
+            let (a, b) = h.edge_endpoints(e).unwrap();
+            let v = map.vertex(e).unwrap();
+            for n in [a, b] {
+                let (s, t) = root.edge_endpoints(map.edge(n).unwrap()).unwrap();
+                assert!(s == v || t == v);
+            }
+        }
+    }
+
+    #[test]
+    fn not_line_graphs() {
+        let claw = UnGraph::<(), ()>::from_edges([(0, 1), (0, 2), (0, 3)]);
+        assert!(root_graph(&claw).is_none());
+        let looped = UnGraph::<(), ()>::from_edges([(0, 1), (1, 1)]);
+        assert!(root_graph(&looped).is_none());
+        let dipole = UnGraph::<(), ()>::from_edges([(0, 1), (0, 1)]);
+        assert!(root_graph(&dipole).is_none());
+        // K5 minus an edge
+        let k5e = UnGraph::<(), ()>::from_edges([
+            (0, 1),
+            (0, 2),
+            (0, 3),
+            (0, 4),
+            (1, 2),
+            (1, 3),
+            (1, 4),
+            (2, 3),
+            (2, 4),
+        ]);
+        assert!(root_graph(&k5e).is_none());
+    }
+}
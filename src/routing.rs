@@ -0,0 +1,212 @@
+use petgraph::{
+    graph::{DiGraph, EdgeIndex, EdgeReference, IndexType, NodeIndex},
+    visit::{EdgeRef, IntoNodeReferences},
+    Direction::{Incoming, Outgoing},
+};
+
+/// Construct the edge-expanded graph for `g` with turn weights
+///
+/// This is the same as [line_digraph](crate::line_digraph), except
+/// that the weight of each transition is computed by `turn_fn(e_in,
+/// e_out, v)`, where `e_in` is the edge arriving at the vertex `v` and
+/// `e_out` is the edge leaving it. If `turn_fn` returns `None`, the
+/// turn is forbidden and there is no edge from `e_in` to `e_out`.
+///
+/// Each edge `e` of `g` becomes the node with index `e.index()` with
+/// the weight of `e`. Use [expanded_path_edges] and
+/// [expanded_path_vertices] to map a path in the edge-expanded graph
+/// back to `g`. Capacity is reserved for a transition between each
+/// pair of edges arriving at and leaving the same vertex, which is an
+/// upper bound for the number of edges.
+///
+/// # Example
+///
+/// Forbid U-turns and penalise all other turns.
+///
+/// ```rust
+/// use line_graph::edge_expanded_graph;
+/// use petgraph::{graph::DiGraph, visit::EdgeRef};
+///
+/// let g = DiGraph::<(), u32>::from_edges([
+///     (0, 1, 5),
+///     (1, 0, 5),
+///     (1, 2, 3),
+/// ]);
+/// let expanded = edge_expanded_graph(&g, |e_in, e_out, _| {
+///     (e_in.source() != e_out.target()).then_some(1 + *e_out.weight())
+/// });
+/// assert_eq!(expanded.edge_count(), 1);
+/// ```
+pub fn edge_expanded_graph<'a, N, E, W, Ix, F>(
+    g: &'a DiGraph<N, E, Ix>,
+    mut turn_fn: F,
+) -> DiGraph<E, W, Ix>
+where
+    E: Clone,
+    Ix: IndexType,
+    F: FnMut(
+        EdgeReference<'a, E, Ix>,
+        EdgeReference<'a, E, Ix>,
+        (NodeIndex<Ix>, &'a N),
+    ) -> Option<W>,
+{
+    // Each pair of incoming and outgoing edges is a potential transition
+    let mut degrees = vec![(0usize, 0usize); g.node_count()];
+    for e in g.edge_references() {
+        degrees[e.target().index()].0 += 1;
+        degrees[e.source().index()].1 += 1;
+    }
+    let transitions = degrees
+        .into_iter()
+        .fold(0usize, |n, (i, o)| n.saturating_add(i.saturating_mul(o)));
+    let mut expanded = DiGraph::with_capacity(g.edge_count(), transitions);
+    for edge in g.edge_references() {
+        expanded.add_node(edge.weight().clone());
+    }
+    for (nidx, nwt) in g.node_references() {
+        for e1 in g.edges_directed(nidx, Incoming) {
+            for e2 in g.edges_directed(nidx, Outgoing) {
+                if let Some(w) = turn_fn(e1, e2, (nidx, nwt)) {
+                    let v1 = NodeIndex::new(e1.id().index());
+                    let v2 = NodeIndex::new(e2.id().index());
+                    expanded.add_edge(v1, v2, w);
+                }
+            }
+        }
+    }
+    expanded
+}
+
+/// The original edges along a path in an edge-expanded graph
+///
+/// `path` is a sequence of nodes in the graph constructed by
+/// [edge_expanded_graph] or [line_digraph](crate::line_digraph).
+pub fn expanded_path_edges<Ix: IndexType>(
+    path: &[NodeIndex<Ix>],
+) -> Vec<EdgeIndex<Ix>> {
+    path.iter().map(|n| EdgeIndex::new(n.index())).collect()
+}
+
+/// The original vertices along a path in an edge-expanded graph
+///
+/// `path` is a sequence of nodes in the graph constructed from `g` by
+/// [edge_expanded_graph] or [line_digraph](crate::line_digraph). The
+/// result starts with the source of the first edge, followed by the
+/// target of each edge. Returns `None` if the path refers to an edge
+/// not in `g` or if consecutive edges are not connected in `g`.
+///
+/// # Example
+///
+/// ```rust
+/// use line_graph::{edge_expanded_graph, expanded_path_vertices};
+/// use petgraph::{algo::astar, graph::{DiGraph, NodeIndex}};
+///
+/// let g = DiGraph::<(), ()>::from_edges([(0, 1), (1, 2), (2, 3)]);
+/// let expanded = edge_expanded_graph(&g, |_, _, _| Some(1));
+/// let (_, path) = astar(
+///     &expanded,
+///     NodeIndex::new(0),
+///     |n| n.index() == 2,
+///     |e| *e.weight(),
+///     |_| 0,
+/// ).unwrap();
+/// let vertices = expanded_path_vertices(&g, &path).unwrap();
+/// assert_eq!(vertices, (0..4).map(NodeIndex::new).collect::<Vec<_>>());
+/// ```
+pub fn expanded_path_vertices<N, E, Ix: IndexType>(
+    g: &DiGraph<N, E, Ix>,
+    path: &[NodeIndex<Ix>],
+) -> Option<Vec<NodeIndex<Ix>>> {
+    let mut vertices = Vec::with_capacity(path.len() + 1);
+    for e in expanded_path_edges(path) {
+        let (s, t) = g.edge_endpoints(e)?;
+        match vertices.last() {
+            None => vertices.push(s),
+            Some(&last) if last != s => return None,
+            _ => {}
+        }
+        vertices.push(t);
+    }
+    Some(vertices)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::line_digraph;
+    use petgraph::algo::{astar, is_isomorphic};
+
+    #[test]
+    fn all_turns() {
+        let g = DiGraph::<(), ()>::from_edges([
+            (0, 1),
+            (1, 2),
+            (2, 0),
+            (1, 0),
+            (2, 2),
+        ]);
+        let expanded = edge_expanded_graph(&g, |_, _, _| Some(()));
+        assert!(is_isomorphic(&expanded, &line_digraph(&g)));
+    }
+
+    // 3x2 grid, every street in both directions
+    //
+    // 0 - 1 - 2
+    // |   |   |
+    // 3 - 4 - 5
+    #[test]
+    fn turn_restrictions() {
+        let mut g = DiGraph::<(), u32>::new();
+        for _ in 0..6 {
+            g.add_node(());
+        }
+        for (a, b) in [(0, 1), (1, 2), (3, 4), (4, 5), (0, 3), (1, 4), (2, 5)] {
+            g.add_edge(NodeIndex::new(a), NodeIndex::new(b), 1);
+            g.add_edge(NodeIndex::new(b), NodeIndex::new(a), 1);
+        }
+        let straight = |e_in: EdgeReference<u32>, e_out: EdgeReference<u32>| {
+            let [a, b, c] = [e_in.source(), e_in.target(), e_out.target()]
+                .map(|n| (n.index() % 3, n.index() / 3));
+            a.0 + c.0 == 2 * b.0 && a.1 + c.1 == 2 * b.1
+        };
+        // turns cost 1, U-turns and the left turn 3 -> 4 -> 1 are
+        // forbidden
+        let expanded = edge_expanded_graph(&g, |e_in, e_out, _| {
+            let turn = (e_in.source(), e_in.target(), e_out.target());
+            if turn.0 == turn.2 || turn == (3.into(), 4.into(), 1.into()) {
+                None
+            } else if straight(e_in, e_out) {
+                Some(0)
+            } else {
+                Some(1)
+            }
+        });
+
+        // from 3 -> 4 to 1, we have to go around 5 -> 2 -> 1
+        let start = g.find_edge(3.into(), 4.into()).unwrap();
+        let (cost, path) = astar(
+            &expanded,
+            NodeIndex::new(start.index()),
+            |n| {
+                g.edge_endpoints(EdgeIndex::new(n.index())).unwrap().1
+                    == 1.into()
+            },
+            |e| *e.weight() + 1,
+            |_| 0,
+        )
+        .unwrap();
+        assert_eq!(cost, 5);
+        let vertices = expanded_path_vertices(&g, &path).unwrap();
+        let vertices: Vec<_> =
+            vertices.into_iter().map(|v| v.index()).collect();
+        assert_eq!(vertices, [3, 4, 5, 2, 1]);
+        let edges = expanded_path_edges(&path);
+        assert_eq!(edges[0], start);
+        assert_eq!(edges.len(), 4);
+
+        assert_eq!(
+            expanded_path_vertices(&g, &[NodeIndex::new(0), NodeIndex::new(0)]),
+            None
+        );
+    }
+}
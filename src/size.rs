@@ -0,0 +1,338 @@
+use crate::{
+    input::edge_references, BudgetExceeded, GraphSize, LineGraphBuilder,
+    LineGraphEdgeRef, LoopPolicy,
+};
+
+use petgraph::{
+    graph::{EdgeIndex, IndexType, UnGraph},
+    visit::{
+        EdgeRef, GraphProp, IntoEdgeReferences, IntoNodeReferences,
+        NodeIndexable,
+    },
+    Undirected,
+};
+use std::{error::Error, fmt};
+
+/// Error indicating that a line graph cannot be constructed
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum LineGraphError {
+    /// The line graph has more nodes or edges than the index type can
+    /// represent
+    IndexOverflow {
+        /// The size of the line graph, with the number of edges
+        /// saturated at `usize::MAX`
+        size: GraphSize,
+        /// The maximum number of nodes and edges supported by the index
+        /// type
+        max: usize,
+    },
+    /// The line graph has more edges than the budget allows
+    BudgetExceeded(BudgetExceeded),
+}
+
+impl fmt::Display for LineGraphError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::IndexOverflow { size, max } => write!(
+                f,
+                "line graph with {} nodes and {} edges exceeds the maximum index {}",
+                size.nodes, size.edges, max
+            ),
+            Self::BudgetExceeded(err) => err.fmt(f),
+        }
+    }
+}
+
+impl Error for LineGraphError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::IndexOverflow { .. } => None,
+            Self::BudgetExceeded(err) => Some(err),
+        }
+    }
+}
+
+impl From<BudgetExceeded> for LineGraphError {
+    fn from(err: BudgetExceeded) -> Self {
+        Self::BudgetExceeded(err)
+    }
+}
+
+/// Construct the line graph for `g` if it is not too large
+///
+/// The size of the line graph is computed from the vertex degrees of
+/// `g` before construction. If the line graph would have more than
+/// `budget` edges, or more nodes or edges than can be indexed with the
+/// index type of `g`, an error is returned. Otherwise, the result is
+/// the same as for [line_graph](crate::line_graph). Use
+/// [LineGraphBuilder::try_build] for other index types.
+///
+/// # Example
+///
+/// ```rust
+/// use line_graph::{try_line_graph, LineGraphError};
+/// use petgraph::graph::UnGraph;
+///
+/// // the star with four edges
+/// let g = UnGraph::<(), ()>::from_edges([(0, 1), (0, 2), (0, 3), (0, 4)]);
+/// assert_eq!(try_line_graph(&g, 6).unwrap().edge_count(), 6);
+/// assert!(matches!(
+///     try_line_graph(&g, 5),
+///     Err(LineGraphError::BudgetExceeded(_))
+/// ));
+/// ```
+#[allow(clippy::type_complexity)]
+pub fn try_line_graph<G>(
+    g: G,
+    budget: usize,
+) -> Result<
+    UnGraph<G::EdgeWeight, G::NodeWeight, <G::EdgeRef as LineGraphEdgeRef>::Ix>,
+    LineGraphError,
+>
+where
+    G: IntoNodeReferences
+        + IntoEdgeReferences
+        + NodeIndexable
+        + GraphProp<EdgeType = Undirected>,
+    G::EdgeRef: LineGraphEdgeRef,
+    G::NodeWeight: Clone,
+    G::EdgeWeight: Clone,
+{
+    LineGraphBuilder::new().try_build(g, budget)
+}
+
+/// The number of edges in the line graph of `g`
+///
+/// This is the sum of `deg(v) choose 2` over all vertices `v` of `g`,
+/// where a self-loop counts once towards the degree of its vertex, as
+/// in [line_graph](crate::line_graph). The result saturates at
+/// `usize::MAX`. Use [LineGraphBuilder::edge_count] for other
+/// self-loop policies.
+///
+/// # Example
+///
+/// ```rust
+/// use line_graph::line_graph_edge_count;
+/// use petgraph::graph::UnGraph;
+///
+/// // the star with four edges
+/// let g = UnGraph::<(), ()>::from_edges([(0, 1), (0, 2), (0, 3), (0, 4)]);
+/// assert_eq!(line_graph_edge_count(&g), 6);
+/// ```
+pub fn line_graph_edge_count<N, E, Ix: IndexType>(
+    g: &UnGraph<N, E, Ix>,
+) -> usize {
+    line_graph_size(g, LoopPolicy::default()).edges
+}
+
+/// The degree of the line graph node corresponding to the edge `e` of
+/// `g`
+///
+/// For an edge between distinct vertices `u` and `v` this is `deg(u) +
+/// deg(v) - 2`, where a self-loop counts once towards the degree of
+/// its vertex, as in [line_graph](crate::line_graph). Returns `None` if
+/// `e` is not an edge of `g`. Use [LineGraphBuilder::degree] for other
+/// self-loop policies.
+///
+/// # Example
+///
+/// ```rust
+/// use line_graph::line_graph_degree;
+/// use petgraph::graph::{EdgeIndex, UnGraph};
+///
+/// let g = UnGraph::<(), ()>::from_edges([(0, 1), (1, 2), (1, 3), (2, 3)]);
+/// assert_eq!(line_graph_degree(&g, EdgeIndex::new(1)), Some(3));
+/// assert_eq!(line_graph_degree(&g, EdgeIndex::new(4)), None);
+/// ```
+pub fn line_graph_degree<N, E, Ix: IndexType>(
+    g: &UnGraph<N, E, Ix>,
+    e: EdgeIndex<Ix>,
+) -> Option<usize> {
+    degree(g, e, LoopPolicy::default())
+}
+
+/// The degree of the line graph node corresponding to `e`, treating
+/// self-loops according to `loops`
+///
+/// A line graph self-loop counts twice.
+pub(crate) fn degree<N, E, Ix: IndexType>(
+    g: &UnGraph<N, E, Ix>,
+    e: EdgeIndex<Ix>,
+    loops: LoopPolicy,
+) -> Option<usize> {
+    let (s, t) = g.edge_endpoints(e)?;
+    let incidences = |v| -> usize {
+        g.edges(v)
+            .map(|e| {
+                if e.source() != e.target() {
+                    1
+                } else {
+                    loop_incidences(loops)
+                }
+            })
+            .sum()
+    };
+    let degree = if s != t {
+        incidences(s) + incidences(t) - 2
+    } else {
+        // each incidence of the loop is adjacent to all other
+        // incidences at its vertex
+        loop_incidences(loops) * incidences(s).saturating_sub(1)
+    };
+    Some(degree)
+}
+
+/// Check that a line graph of the given size fits into the budget and
+/// can be indexed with `Ix`
+pub(crate) fn check_size<Ix: IndexType>(
+    size: GraphSize,
+    budget: usize,
+) -> Result<(), LineGraphError> {
+    let max = <Ix as IndexType>::max().index();
+    if size.nodes > max || size.edges > max {
+        return Err(LineGraphError::IndexOverflow { size, max });
+    }
+    if size.edges > budget {
+        return Err(LineGraphError::BudgetExceeded(BudgetExceeded {
+            iteration: 1,
+            edges: size.edges,
+            budget,
+        }));
+    }
+    Ok(())
+}
+
+/// Size of the line graph of `g`, treating self-loops according to
+/// `loops`
+pub(crate) fn line_graph_size<G>(g: G, loops: LoopPolicy) -> GraphSize
+where
+    G: IntoEdgeReferences + NodeIndexable,
+    G::EdgeRef: LineGraphEdgeRef,
+{
+    let edges = incidences(g, loops)
+        .into_iter()
+        .try_fold(0usize, |acc, d| acc.checked_add(choose_2(d)?))
+        .unwrap_or(usize::MAX);
+    GraphSize {
+        nodes: edge_references(g).count(),
+        edges,
+    }
+}
+
+/// Number of edges incident to each vertex, counting loops according
+/// to `loops`
+pub(crate) fn incidences<G>(g: G, loops: LoopPolicy) -> Vec<usize>
+where
+    G: IntoEdgeReferences + NodeIndexable,
+    G::EdgeRef: LineGraphEdgeRef,
+{
+    let mut incidences = vec![0; g.node_bound()];
+    for e in edge_references(g) {
+        let s = NodeIndexable::to_index(&g, e.source());
+        let t = NodeIndexable::to_index(&g, e.target());
+        if s != t {
+            incidences[s] += 1;
+            incidences[t] += 1;
+        } else {
+            incidences[s] += loop_incidences(loops);
+        }
+    }
+    incidences
+}
+
+/// Number of times a self-loop is incident to its vertex
+fn loop_incidences(loops: LoopPolicy) -> usize {
+    match loops {
+        LoopPolicy::Ignore => 0,
+        LoopPolicy::Once => 1,
+        LoopPolicy::Twice => 2,
+    }
+}
+
+pub(crate) fn choose_2(n: usize) -> Option<usize> {
+    let n = n as u128;
+    usize::try_from(n * n.saturating_sub(1) / 2).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::line_graph;
+    use petgraph::{graph::NodeIndex, graphmap::UnGraphMap};
+
+    #[test]
+    fn budget() {
+        let g = UnGraph::<(), ()>::from_edges([(0, 1), (1, 1), (1, 2), (1, 2)]);
+        let line = try_line_graph(&g, 7).unwrap();
+        assert_eq!(line.edge_count(), 7);
+        let err = try_line_graph(&g, 6).unwrap_err();
+        assert_eq!(
+            err,
+            LineGraphError::BudgetExceeded(BudgetExceeded {
+                iteration: 1,
+                edges: 7,
+                budget: 6
+            })
+        );
+
+        let g = UnGraphMap::<u8, ()>::from_edges([(0, 1), (1, 2), (2, 0)]);
+        assert_eq!(try_line_graph(&g, 3).unwrap().edge_count(), 3);
+    }
+
+    #[test]
+    fn counts() {
+        let g = UnGraph::<(), ()>::from_edges([
+            (0, 1),
+            (0, 1),
+            (1, 1),
+            (1, 2),
+            (2, 2),
+            (2, 2),
+            (2, 3),
+        ]);
+        for loops in [LoopPolicy::Ignore, LoopPolicy::Once, LoopPolicy::Twice] {
+            let builder = LineGraphBuilder::new().loops(loops);
+            let line = builder.build(&g);
+            assert_eq!(builder.edge_count(&g), line.edge_count());
+            for e in g.edge_indices() {
+                let n = NodeIndex::new(e.index());
+                let degree = line
+                    .edge_references()
+                    .map(|l| {
+                        (l.source() == n) as usize + (l.target() == n) as usize
+                    })
+                    .sum();
+                assert_eq!(builder.degree(&g, e), Some(degree));
+            }
+        }
+        assert_eq!(line_graph_edge_count(&g), line_graph(&g).edge_count());
+        assert_eq!(line_graph_degree(&g, EdgeIndex::new(2)), Some(3));
+        assert_eq!(line_graph_degree(&g, EdgeIndex::new(7)), None);
+    }
+
+    #[test]
+    fn overflow() {
+        let size = GraphSize {
+            nodes: 70000,
+            edges: 70000 * 69999 / 2,
+        };
+        assert!(check_size::<u32>(size, usize::MAX).is_ok());
+        assert_eq!(
+            check_size::<u16>(size, 0),
+            Err(LineGraphError::IndexOverflow {
+                size,
+                max: u16::MAX as usize
+            })
+        );
+
+        // star with 2^17 edges: its line graph has more than 2^32 edges
+        let mut g = UnGraph::<(), ()>::with_capacity(1 << 17, 1 << 17);
+        let center = g.add_node(());
+        for _ in 0..(1 << 17) {
+            let n = g.add_node(());
+            g.add_edge(center, n, ());
+        }
+        let err = try_line_graph(&g, usize::MAX).unwrap_err();
+        assert!(matches!(err, LineGraphError::IndexOverflow { .. }));
+    }
+}
@@ -0,0 +1,51 @@
+use petgraph::graph::UnGraph;
+
+/// Deterministic pseudo-random simple graph
+///
+/// Self-loops and parallel edges are skipped, so the result can have
+/// fewer than `edges` edges.
+pub(crate) fn random_graph(
+    nodes: u32,
+    edges: usize,
+    seed: u64,
+) -> UnGraph<(), ()> {
+    random_edges(nodes, edges, seed, |g, a, b| {
+        a != b && !g.contains_edge(a.into(), b.into())
+    })
+}
+
+/// Deterministic pseudo-random graph, possibly with self-loops and
+/// parallel edges
+pub(crate) fn random_multigraph(
+    nodes: u32,
+    edges: usize,
+    seed: u64,
+) -> UnGraph<(), ()> {
+    random_edges(nodes, edges, seed, |_, _, _| true)
+}
+
+fn random_edges(
+    nodes: u32,
+    edges: usize,
+    seed: u64,
+    mut accept: impl FnMut(&UnGraph<(), ()>, u32, u32) -> bool,
+) -> UnGraph<(), ()> {
+    let mut state = seed;
+    let mut next = || {
+        state = state
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        ((state >> 33) % nodes as u64) as u32
+    };
+    let mut g = UnGraph::with_capacity(nodes as usize, edges);
+    for _ in 0..nodes {
+        g.add_node(());
+    }
+    for _ in 0..edges {
+        let (a, b) = (next(), next());
+        if accept(&g, a, b) {
+            g.add_edge(a.into(), b.into(), ());
+        }
+    }
+    g
+}
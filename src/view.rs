@@ -0,0 +1,451 @@
+use crate::line_graph_edge_count;
+
+use fixedbitset::FixedBitSet;
+use petgraph::{
+    graph::{self, DefaultIx, EdgeIndex, EdgeIndices, IndexType, NodeIndex},
+    visit::{
+        Data, EdgeCount, EdgeRef, GraphBase, GraphProp, GraphRef,
+        IntoEdgeReferences, IntoEdges, IntoNeighbors, IntoNodeIdentifiers,
+        IntoNodeReferences, NodeCompactIndexable, NodeCount, NodeIndexable,
+        VisitMap, Visitable,
+    },
+    Undirected,
+};
+
+/// Line graph of an undirected graph without materialising it
+///
+/// `LineGraphView` implements petgraph's visit traits such that
+/// algorithms like [dijkstra](petgraph::algo::dijkstra()),
+/// [Bfs](petgraph::visit::Bfs), or
+/// [connected_components](petgraph::algo::connected_components) can be
+/// run directly on the line graph. The nodes of the view are the edge
+/// indices of the original graph. Each line graph edge refers to the
+/// shared vertex and has its weight.
+///
+/// The view is consistent with [line_graph](crate::line_graph): a
+/// self-loop is incident to its vertex once and each pair of parallel
+/// edges is connected by two line graph edges. Neighbours and edges
+/// are computed on the fly from the original graph, so iterating over
+/// the edges of a line graph node takes time proportional to the degree
+/// of its endpoints. Iterating over all edges with
+/// [edge_references](IntoEdgeReferences::edge_references) takes time
+/// proportional to the number of line graph edges. An edge index that
+/// does not belong to the original graph has no edges or neighbours.
+///
+/// # Example
+///
+/// ```rust
+/// use line_graph::LineGraphView;
+/// use petgraph::{algo::dijkstra, graph::{EdgeIndex, UnGraph}};
+///
+/// // path 0 - 1 - 2 - 3
+/// let g = UnGraph::<(), ()>::from_edges([(0, 1), (1, 2), (2, 3)]);
+/// let view = LineGraphView::new(&g);
+/// let dist = dijkstra(view, EdgeIndex::new(0), None, |_| 1);
+/// assert_eq!(dist[&EdgeIndex::new(2)], 2);
+/// ```
+#[derive(Debug)]
+pub struct LineGraphView<'a, N, E, Ix: IndexType = DefaultIx> {
+    graph: &'a graph::UnGraph<N, E, Ix>,
+}
+
+impl<'a, N, E, Ix: IndexType> LineGraphView<'a, N, E, Ix> {
+    /// Create a view of the line graph of `graph`
+    pub fn new(graph: &'a graph::UnGraph<N, E, Ix>) -> Self {
+        Self { graph }
+    }
+
+    /// The original graph
+    pub fn graph(&self) -> &'a graph::UnGraph<N, E, Ix> {
+        self.graph
+    }
+}
+
+impl<N, E, Ix: IndexType> Clone for LineGraphView<'_, N, E, Ix> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<N, E, Ix: IndexType> Copy for LineGraphView<'_, N, E, Ix> {}
+
+impl<N, E, Ix: IndexType> GraphBase for LineGraphView<'_, N, E, Ix> {
+    type NodeId = EdgeIndex<Ix>;
+    /// The two connected line graph nodes, in ascending order, and the
+    /// shared vertex
+    type EdgeId = (EdgeIndex<Ix>, EdgeIndex<Ix>, NodeIndex<Ix>);
+}
+
+impl<N, E, Ix: IndexType> GraphRef for LineGraphView<'_, N, E, Ix> {}
+
+impl<N, E, Ix: IndexType> Data for LineGraphView<'_, N, E, Ix> {
+    type NodeWeight = E;
+    type EdgeWeight = N;
+}
+
+impl<N, E, Ix: IndexType> GraphProp for LineGraphView<'_, N, E, Ix> {
+    type EdgeType = Undirected;
+}
+
+impl<N, E, Ix: IndexType> NodeCount for LineGraphView<'_, N, E, Ix> {
+    fn node_count(&self) -> usize {
+        self.graph.edge_count()
+    }
+}
+
+impl<N, E, Ix: IndexType> EdgeCount for LineGraphView<'_, N, E, Ix> {
+    fn edge_count(&self) -> usize {
+        line_graph_edge_count(self.graph)
+    }
+}
+
+impl<N, E, Ix: IndexType> NodeIndexable for LineGraphView<'_, N, E, Ix> {
+    fn node_bound(&self) -> usize {
+        self.graph.edge_count()
+    }
+
+    fn to_index(&self, a: Self::NodeId) -> usize {
+        a.index()
+    }
+
+    fn from_index(&self, i: usize) -> Self::NodeId {
+        EdgeIndex::new(i)
+    }
+}
+
+impl<N, E, Ix: IndexType> NodeCompactIndexable for LineGraphView<'_, N, E, Ix> {}
+
+impl<N, E, Ix: IndexType> Visitable for LineGraphView<'_, N, E, Ix> {
+    type Map = LineVisitMap;
+
+    fn visit_map(&self) -> Self::Map {
+        LineVisitMap(FixedBitSet::with_capacity(self.graph.edge_count()))
+    }
+
+    fn reset_map(&self, map: &mut Self::Map) {
+        map.0.clear();
+        map.0.grow(self.graph.edge_count());
+    }
+}
+
+impl<'a, N, E, Ix: IndexType> IntoNodeIdentifiers
+    for LineGraphView<'a, N, E, Ix>
+{
+    type NodeIdentifiers = EdgeIndices<Ix>;
+
+    fn node_identifiers(self) -> Self::NodeIdentifiers {
+        self.graph.edge_indices()
+    }
+}
+
+impl<'a, N, E, Ix: IndexType> IntoNodeReferences
+    for LineGraphView<'a, N, E, Ix>
+{
+    type NodeRef = (EdgeIndex<Ix>, &'a E);
+    type NodeReferences = LineNodeReferences<'a, E, Ix>;
+
+    fn node_references(self) -> Self::NodeReferences {
+        LineNodeReferences {
+            iter: self.graph.edge_references(),
+        }
+    }
+}
+
+impl<'a, N, E, Ix: IndexType> IntoNeighbors for LineGraphView<'a, N, E, Ix> {
+    type Neighbors = LineNeighbors<'a, N, E, Ix>;
+
+    fn neighbors(self, a: Self::NodeId) -> Self::Neighbors {
+        LineNeighbors {
+            iter: self.edges(a),
+        }
+    }
+}
+
+impl<'a, N, E, Ix: IndexType> IntoEdges for LineGraphView<'a, N, E, Ix> {
+    type Edges = LineEdges<'a, N, E, Ix>;
+
+    fn edges(self, a: Self::NodeId) -> Self::Edges {
+        let endpoints = self.graph.edge_endpoints(a);
+        LineEdges {
+            graph: self.graph,
+            edge: a,
+            current: endpoints.map(|(s, _)| (s, self.graph.edges(s))),
+            next: endpoints.and_then(|(s, t)| (s != t).then_some(t)),
+        }
+    }
+}
+
+impl<'a, N, E, Ix: IndexType> IntoEdgeReferences
+    for LineGraphView<'a, N, E, Ix>
+{
+    type EdgeRef = LineEdgeReference<'a, N, Ix>;
+    type EdgeReferences = LineEdgeReferences<'a, N, E, Ix>;
+
+    fn edge_references(self) -> Self::EdgeReferences {
+        LineEdgeReferences {
+            graph: self.graph,
+            vertices: self.graph.node_indices(),
+            vertex: NodeIndex::end(),
+            incident: Vec::new(),
+            pos: (0, 0),
+        }
+    }
+}
+
+/// Edge of a [LineGraphView]
+#[derive(Debug)]
+pub struct LineEdgeReference<'a, N, Ix: IndexType = DefaultIx> {
+    source: EdgeIndex<Ix>,
+    target: EdgeIndex<Ix>,
+    vertex: NodeIndex<Ix>,
+    weight: &'a N,
+}
+
+impl<N, Ix: IndexType> LineEdgeReference<'_, N, Ix> {
+    /// The original vertex shared by the source and target edges
+    pub fn vertex(&self) -> NodeIndex<Ix> {
+        self.vertex
+    }
+}
+
+impl<N, Ix: IndexType> Clone for LineEdgeReference<'_, N, Ix> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<N, Ix: IndexType> Copy for LineEdgeReference<'_, N, Ix> {}
+
+impl<N, Ix: IndexType> EdgeRef for LineEdgeReference<'_, N, Ix> {
+    type NodeId = EdgeIndex<Ix>;
+    type EdgeId = (EdgeIndex<Ix>, EdgeIndex<Ix>, NodeIndex<Ix>);
+    type Weight = N;
+
+    fn source(&self) -> Self::NodeId {
+        self.source
+    }
+
+    fn target(&self) -> Self::NodeId {
+        self.target
+    }
+
+    fn weight(&self) -> &N {
+        self.weight
+    }
+
+    fn id(&self) -> Self::EdgeId {
+        let (s, t) = (self.source, self.target);
+        (s.min(t), s.max(t), self.vertex)
+    }
+}
+
+/// Iterator over the nodes of a [LineGraphView]
+#[derive(Debug, Clone)]
+pub struct LineNodeReferences<'a, E, Ix: IndexType = DefaultIx> {
+    iter: graph::EdgeReferences<'a, E, Ix>,
+}
+
+impl<'a, E, Ix: IndexType> Iterator for LineNodeReferences<'a, E, Ix> {
+    type Item = (EdgeIndex<Ix>, &'a E);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|e| (e.id(), e.weight()))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+/// Iterator over the edges of a node in a [LineGraphView]
+pub struct LineEdges<'a, N, E, Ix: IndexType = DefaultIx> {
+    graph: &'a graph::UnGraph<N, E, Ix>,
+    edge: EdgeIndex<Ix>,
+    current: Option<(NodeIndex<Ix>, graph::Edges<'a, E, Undirected, Ix>)>,
+    next: Option<NodeIndex<Ix>>,
+}
+
+impl<'a, N, E, Ix: IndexType> Iterator for LineEdges<'a, N, E, Ix> {
+    type Item = LineEdgeReference<'a, N, Ix>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (vertex, edges) = self.current.as_mut()?;
+            let vertex = *vertex;
+            if let Some(e) = edges.find(|e| e.id() != self.edge) {
+                return Some(LineEdgeReference {
+                    source: self.edge,
+                    target: e.id(),
+                    vertex,
+                    weight: &self.graph[vertex],
+                });
+            }
+            self.current = self.next.take().map(|v| (v, self.graph.edges(v)));
+        }
+    }
+}
+
+/// Iterator over the neighbours of a node in a [LineGraphView]
+pub struct LineNeighbors<'a, N, E, Ix: IndexType = DefaultIx> {
+    iter: LineEdges<'a, N, E, Ix>,
+}
+
+impl<N, E, Ix: IndexType> Iterator for LineNeighbors<'_, N, E, Ix> {
+    type Item = EdgeIndex<Ix>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|e| e.target)
+    }
+}
+
+/// Iterator over all edges of a [LineGraphView]
+///
+/// The edges are produced in the same order as by
+/// [line_graph](crate::line_graph).
+pub struct LineEdgeReferences<'a, N, E, Ix: IndexType = DefaultIx> {
+    graph: &'a graph::UnGraph<N, E, Ix>,
+    vertices: graph::NodeIndices<Ix>,
+    vertex: NodeIndex<Ix>,
+    incident: Vec<EdgeIndex<Ix>>,
+    pos: (usize, usize),
+}
+
+impl<'a, N, E, Ix: IndexType> Iterator for LineEdgeReferences<'a, N, E, Ix> {
+    type Item = LineEdgeReference<'a, N, Ix>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (mut s, mut t) = self.pos;
+        while s + 1 >= self.incident.len() {
+            self.vertex = self.vertices.next()?;
+            self.incident.clear();
+            self.incident
+                .extend(self.graph.edges(self.vertex).map(|e| e.id()));
+            self.incident.sort_unstable();
+            (s, t) = (0, 1);
+        }
+        let res = LineEdgeReference {
+            source: self.incident[s],
+            target: self.incident[t],
+            vertex: self.vertex,
+            weight: &self.graph[self.vertex],
+        };
+        t += 1;
+        if t == self.incident.len() {
+            s += 1;
+            t = s + 1;
+        }
+        self.pos = (s, t);
+        Some(res)
+    }
+}
+
+/// Visit map of a [LineGraphView]
+///
+/// A bit set indexed by the nodes of the view, that is the edge indices
+/// of the original graph.
+#[derive(Clone, Debug, Default)]
+pub struct LineVisitMap(FixedBitSet);
+
+impl<Ix: IndexType> VisitMap<EdgeIndex<Ix>> for LineVisitMap {
+    fn visit(&mut self, a: EdgeIndex<Ix>) -> bool {
+        !self.0.put(a.index())
+    }
+
+    fn is_visited(&self, a: &EdgeIndex<Ix>) -> bool {
+        self.0.contains(a.index())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::line_graph_with_map;
+    use petgraph::{
+        algo::{connected_components, dijkstra},
+        graph::UnGraph,
+        visit::Bfs,
+    };
+
+    #[test]
+    fn consistent() {
+        let g = UnGraph::<u32, u32>::from_edges([
+            (0, 1, 0),
+            (0, 1, 1),
+            (1, 1, 2),
+            (1, 2, 3),
+            (2, 3, 4),
+            (3, 3, 5),
+            (4, 5, 6),
+        ]);
+        let (line, map) = line_graph_with_map(&g);
+        let view = LineGraphView::new(&g);
+        assert_eq!(view.node_count(), line.node_count());
+        assert_eq!(view.edge_count(), line.edge_count());
+
+        let edges: Vec<_> = view
+            .edge_references()
+            .map(|e| (e.source().index(), e.target().index(), e.vertex()))
+            .collect();
+        let expected: Vec<_> = line
+            .edge_references()
+            .map(|e| {
+                let v = map.vertex(e.id()).unwrap();
+                (e.source().index(), e.target().index(), v)
+            })
+            .collect();
+        assert_eq!(edges, expected);
+
+        for n in line.node_indices() {
+            let e = map.edge(n).unwrap();
+            let mut neighbours: Vec<_> =
+                view.neighbors(e).map(|e| e.index()).collect();
+            let mut expected: Vec<_> =
+                line.neighbors(n).map(|n| n.index()).collect();
+            neighbours.sort();
+            expected.sort();
+            assert_eq!(neighbours, expected);
+            for edge in view.edges(e) {
+                assert_eq!(edge.source(), e);
+                assert_eq!(*edge.weight(), g[edge.vertex()]);
+            }
+        }
+    }
+
+    #[test]
+    fn algorithms() {
+        let g = UnGraph::<(), ()>::from_edges([
+            (0, 1),
+            (1, 2),
+            (2, 3),
+            (3, 4),
+            (1, 5),
+            (6, 7),
+            (7, 8),
+        ]);
+        let view = LineGraphView::new(&g);
+        assert_eq!(connected_components(view), 2);
+
+        let dist = dijkstra(view, EdgeIndex::new(0), None, |_| 1);
+        let dist: Vec<_> =
+            g.edge_indices().map(|e| dist.get(&e).copied()).collect();
+        assert_eq!(
+            dist,
+            [Some(0), Some(1), Some(2), Some(3), Some(1), None, None]
+        );
+
+        let mut bfs = Bfs::new(view, EdgeIndex::new(5));
+        let mut visited = Vec::new();
+        while let Some(e) = bfs.next(view) {
+            visited.push(e.index());
+        }
+        assert_eq!(visited, [5, 6]);
+    }
+
+    #[test]
+    fn unknown_node() {
+        let g = UnGraph::<(), ()>::from_edges([(0, 1), (1, 2)]);
+        let view = LineGraphView::new(&g);
+        assert_eq!(view.edges(EdgeIndex::new(2)).count(), 0);
+        assert_eq!(view.neighbors(EdgeIndex::new(2)).count(), 0);
+    }
+}
@@ -0,0 +1,414 @@
+use petgraph::{
+    graph::{DefaultIx, EdgeIndex, IndexType, NodeIndex, UnGraph},
+    visit::EdgeRef,
+};
+use std::{error::Error, fmt};
+
+/// Reason why an isomorphism of line graphs cannot be lifted
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum LiftError<Ix = DefaultIx> {
+    /// The map is not a bijection between the edges of the two graphs
+    InvalidMap,
+    /// The component of the first graph containing the given edge is
+    /// a triangle and its image is a claw, or the other way round
+    ///
+    /// The edge is the one with the lowest index in its component.
+    TriangleClaw(EdgeIndex<Ix>),
+    /// On the component of the first graph containing the given edge,
+    /// the map is not induced by a map between vertices
+    ///
+    /// The edge is the one with the lowest index in its component.
+    NotInduced(EdgeIndex<Ix>),
+    /// The graphs have different numbers of isolated vertices
+    IsolatedVertices,
+}
+
+impl<Ix: IndexType> fmt::Display for LiftError<Ix> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidMap => write!(f, "map is not a bijection of edges"),
+            Self::TriangleClaw(e) => write!(
+                f,
+                "component of edge {} is mapped between a triangle and a claw",
+                e.index()
+            ),
+            Self::NotInduced(e) => write!(
+                f,
+                "map is not induced by a vertex map on the component of edge {}",
+                e.index()
+            ),
+            Self::IsolatedVertices => {
+                write!(f, "different numbers of isolated vertices")
+            }
+        }
+    }
+}
+
+impl<Ix: IndexType> Error for LiftError<Ix> {}
+
+/// Lift an isomorphism between line graphs to the original graphs
+///
+/// `iso` maps the nodes of the line graph of `g1` to the nodes of the
+/// line graph of `g2`, in the format returned by petgraph's
+/// [subgraph_isomorphisms_iter](petgraph::algo::subgraph_isomorphisms_iter)
+/// for graphs of the same size: node `i` is mapped to node `iso[i]`.
+/// Since each node of a [line graph](crate::line_graph) has the index
+/// of the corresponding edge, `iso` is equivalently a map from the
+/// edges of `g1` to the edges of `g2`.
+///
+/// Returns the map between vertices that induces `iso`, where vertex
+/// `i` of `g1` is mapped to the `i`th entry of the result. Isolated
+/// vertices of `g1` are mapped to isolated vertices of `g2` in
+/// ascending order.
+///
+/// By Whitney's theorem, every isomorphism between the line graphs of
+/// connected simple graphs is induced by an isomorphism of the graphs
+/// themselves if they have more than four vertices. The exceptions
+/// are
+/// - the triangle and the claw `K_{1,3}`, which have the same line
+///   graph. In this case, [LiftError::TriangleClaw] is returned.
+/// - the complete graph `K_4`, `K_4` minus one edge, and the triangle
+///   with a pendant edge, whose line graphs have additional
+///   automorphisms. These may be [not induced](LiftError::NotInduced).
+///
+/// Each component is lifted separately. The same error as for the
+/// small exceptions is returned if `iso` is not actually an
+/// isomorphism between the line graphs. Graphs with self-loops and
+/// parallel edges are allowed, but note that an isomorphism between
+/// their line graphs is often not induced.
+///
+/// The running time is linear in the size of `g1` and `g2`.
+///
+/// # Example
+///
+/// ```rust
+/// use line_graph::{lift_isomorphism, line_graph};
+/// use petgraph::{algo::subgraph_isomorphisms_iter, graph::UnGraph};
+///
+/// let g1 = UnGraph::<(), ()>::from_edges([(0, 1), (1, 2), (2, 3), (3, 4)]);
+/// let g2 = UnGraph::<(), ()>::from_edges([(4, 3), (3, 2), (2, 0), (0, 1)]);
+/// let (l1, l2) = (line_graph(&g1), line_graph(&g2));
+/// let iso = subgraph_isomorphisms_iter(
+///     &&l1,
+///     &&l2,
+///     &mut |_, _| true,
+///     &mut |_, _| true,
+/// )
+/// .unwrap()
+/// .next()
+/// .unwrap();
+/// let vertices = lift_isomorphism(&g1, &g2, &iso).unwrap();
+/// for e in g1.edge_indices() {
+///     let (s, t) = g1.edge_endpoints(e).unwrap();
+///     let (vs, vt) = (vertices[s.index()], vertices[t.index()]);
+///     assert!(g2.contains_edge(vs, vt));
+/// }
+/// ```
+pub fn lift_isomorphism<N1, E1, N2, E2, Ix: IndexType>(
+    g1: &UnGraph<N1, E1, Ix>,
+    g2: &UnGraph<N2, E2, Ix>,
+    iso: &[usize],
+) -> Result<Vec<NodeIndex<Ix>>, LiftError<Ix>> {
+    if iso.len() != g1.edge_count() || iso.len() != g2.edge_count() {
+        return Err(LiftError::InvalidMap);
+    }
+    let mut in_image = vec![false; iso.len()];
+    for &f in iso {
+        if f >= iso.len() || std::mem::replace(&mut in_image[f], true) {
+            return Err(LiftError::InvalidMap);
+        }
+    }
+    let image = |e: EdgeIndex<Ix>| {
+        g2.edge_endpoints(EdgeIndex::new(iso[e.index()])).unwrap()
+    };
+
+    let mut vertices = vec![None; g1.node_count()];
+    let mut used = vec![false; g2.node_count()];
+    let mut visited = vec![false; g1.node_count()];
+    let mut component = Vec::new();
+    let mut component_edges = Vec::new();
+    let mut edge_seen = vec![false; g1.edge_count()];
+    for start in g1.node_indices() {
+        if visited[start.index()] || g1.edges(start).next().is_none() {
+            continue;
+        }
+        component.clear();
+        component_edges.clear();
+        visited[start.index()] = true;
+        component.push(start);
+        let mut pos = 0;
+        while let Some(&v) = component.get(pos) {
+            pos += 1;
+            for e in g1.edges(v) {
+                if !std::mem::replace(&mut edge_seen[e.id().index()], true) {
+                    component_edges.push(e.id());
+                }
+                let w = e.target();
+                if !std::mem::replace(&mut visited[w.index()], true) {
+                    component.push(w);
+                }
+            }
+        }
+
+        let lifted =
+            lift_component(g1, &component, image, &mut vertices, &mut used);
+        if lifted.is_none() {
+            let e = *component_edges.iter().min().unwrap();
+            let shapes = (
+                shape(
+                    component_edges
+                        .iter()
+                        .map(|&e| g1.edge_endpoints(e).unwrap()),
+                ),
+                shape(component_edges.iter().map(|&e| image(e))),
+            );
+            return Err(match shapes {
+                (Some(Shape::Triangle), Some(Shape::Claw))
+                | (Some(Shape::Claw), Some(Shape::Triangle)) => {
+                    LiftError::TriangleClaw(e)
+                }
+                _ => LiftError::NotInduced(e),
+            });
+        }
+    }
+
+    // all remaining vertices of `g2` are isolated, since every edge of
+    // `g2` is the image of some edge of `g1`
+    let mut isolated = g2.node_indices().filter(|v| !used[v.index()]);
+    for v in &mut vertices {
+        if v.is_none() {
+            *v = isolated.next();
+            if v.is_none() {
+                return Err(LiftError::IsolatedVertices);
+            }
+        }
+    }
+    if isolated.next().is_some() {
+        return Err(LiftError::IsolatedVertices);
+    }
+    Ok(vertices.into_iter().map(Option::unwrap).collect())
+}
+
+// Determine the images of the vertices in a connected component of `g`
+//
+// Returns `None` if the edge map restricted to the component is not
+// induced by an injective vertex map.
+fn lift_component<N, E, Ix: IndexType>(
+    g: &UnGraph<N, E, Ix>,
+    component: &[NodeIndex<Ix>],
+    image: impl Fn(EdgeIndex<Ix>) -> (NodeIndex<Ix>, NodeIndex<Ix>),
+    vertices: &mut [Option<NodeIndex<Ix>>],
+    used: &mut [bool],
+) -> Option<()> {
+    // The image of a vertex is an endpoint of the images of all incident
+    // edges. Start from a vertex where this determines the image
+    // uniquely. If there is none, all edges of the component are mapped
+    // to edges between the same two vertices and we can choose either.
+    let candidates = |v: NodeIndex<Ix>| {
+        let mut edges = g.edges(v);
+        let (c, d) = image(edges.next().unwrap().id());
+        let mut candidates = [Some(c), Some(d).filter(|&d| d != c)];
+        for e in edges {
+            let (c, d) = image(e.id());
+            for candidate in &mut candidates {
+                *candidate = candidate.filter(|&x| x == c || x == d);
+            }
+        }
+        candidates
+    };
+    let (start, first) = component
+        .iter()
+        .find_map(|&v| match candidates(v) {
+            [Some(x), None] | [None, Some(x)] => Some((v, x)),
+            _ => None,
+        })
+        .or_else(|| {
+            let [c, d] = candidates(component[0]);
+            Some((component[0], c.or(d)?))
+        })?;
+
+    vertices[start.index()] = Some(first);
+    used[first.index()] = true;
+    let mut stack = vec![start];
+    while let Some(v) = stack.pop() {
+        let x = vertices[v.index()].unwrap();
+        for e in g.edges(v) {
+            let y = match image(e.id()) {
+                (c, d) if c == x => d,
+                (c, d) if d == x => c,
+                _ => return None,
+            };
+            let w = e.target();
+            match vertices[w.index()] {
+                Some(z) if z != y => return None,
+                Some(_) => {}
+                None => {
+                    if std::mem::replace(&mut used[y.index()], true) {
+                        return None;
+                    }
+                    vertices[w.index()] = Some(y);
+                    stack.push(w);
+                }
+            }
+        }
+    }
+    Some(())
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Shape {
+    Triangle,
+    Claw,
+}
+
+fn shape<Ix: IndexType>(
+    edges: impl Iterator<Item = (NodeIndex<Ix>, NodeIndex<Ix>)>,
+) -> Option<Shape> {
+    let edges: Vec<_> = edges.collect();
+    if edges.len() != 3 || edges.iter().any(|(s, t)| s == t) {
+        return None;
+    }
+    let mut vertices: Vec<_> =
+        edges.iter().flat_map(|&(s, t)| [s, t]).collect();
+    vertices.sort_unstable();
+    vertices.dedup();
+    let degree = |v| edges.iter().filter(|&&(s, t)| s == v || t == v).count();
+    match vertices.len() {
+        3 if vertices.iter().all(|&v| degree(v) == 2) => Some(Shape::Triangle),
+        4 if vertices.iter().any(|&v| degree(v) == 3) => Some(Shape::Claw),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{line_graph, test_util::random_multigraph};
+    use petgraph::algo::subgraph_isomorphisms_iter;
+
+    fn assert_induced(
+        g1: &UnGraph<(), ()>,
+        g2: &UnGraph<(), ()>,
+        iso: &[usize],
+        vertices: &[NodeIndex],
+    ) {
+        let mut sorted = vertices.to_vec();
+        sorted.sort();
+        assert!(sorted.iter().copied().eq(g2.node_indices()));
+        for e in g1.edge_indices() {
+            let (s, t) = g1.edge_endpoints(e).unwrap();
+            let (s, t) = (vertices[s.index()], vertices[t.index()]);
+            let (c, d) =
+                g2.edge_endpoints(EdgeIndex::new(iso[e.index()])).unwrap();
+            assert!((s, t) == (c, d) || (s, t) == (d, c));
+        }
+    }
+
+    #[test]
+    fn relabelled() {
+        for seed in 0..200 {
+            let g1 = random_multigraph(10, 5 + (seed % 15) as usize, seed);
+            // reverse the vertices and rotate the edges
+            let n = g1.node_count();
+            let m = g1.edge_count();
+            let mut g2 = UnGraph::<(), ()>::default();
+            for _ in 0..n {
+                g2.add_node(());
+            }
+            let rev = |v: NodeIndex| NodeIndex::new(n - 1 - v.index());
+            for i in 0..m {
+                let e = EdgeIndex::new((i + 3) % m);
+                let (s, t) = g1.edge_endpoints(e).unwrap();
+                g2.add_edge(rev(s), rev(t), ());
+            }
+            let iso: Vec<_> = (0..m).map(|i| (i + m - 3) % m).collect();
+            let vertices = lift_isomorphism(&g1, &g2, &iso).unwrap();
+            assert_induced(&g1, &g2, &iso, &vertices);
+        }
+    }
+
+    fn line_graph_automorphisms(g: &UnGraph<(), ()>) -> Vec<Vec<usize>> {
+        let l = line_graph(g);
+        let (mut node_match, mut edge_match) =
+            (|_: &_, _: &_| true, |_: &_, _: &_| true);
+        subgraph_isomorphisms_iter(&&l, &&l, &mut node_match, &mut edge_match)
+            .unwrap()
+            .collect()
+    }
+
+    #[test]
+    fn whitney() {
+        // connected with more than four vertices
+        let g1 = UnGraph::<(), ()>::from_edges([
+            (0, 1),
+            (0, 2),
+            (1, 2),
+            (2, 3),
+            (3, 4),
+            (4, 0),
+        ]);
+        let isos = line_graph_automorphisms(&g1);
+        assert_eq!(isos.len(), 2);
+        for iso in isos {
+            let vertices = lift_isomorphism(&g1, &g1, &iso).unwrap();
+            assert_induced(&g1, &g1, &iso, &vertices);
+        }
+    }
+
+    #[test]
+    fn exceptions() {
+        let triangle = UnGraph::<(), ()>::from_edges([(0, 1), (1, 2), (2, 0)]);
+        let claw = UnGraph::<(), ()>::from_edges([(0, 1), (0, 2), (0, 3)]);
+        let iso = [0, 1, 2];
+        assert!(lift_isomorphism(&triangle, &triangle, &iso).is_ok());
+        assert!(lift_isomorphism(&claw, &claw, &iso).is_ok());
+        assert_eq!(
+            lift_isomorphism(&triangle, &claw, &iso),
+            Err(LiftError::TriangleClaw(EdgeIndex::new(0)))
+        );
+        assert_eq!(
+            lift_isomorphism(&claw, &triangle, &iso),
+            Err(LiftError::TriangleClaw(EdgeIndex::new(0)))
+        );
+
+        // the line graph of K_4 has 48 automorphisms, K_4 only 24
+        let k4 = UnGraph::<(), ()>::from_edges([
+            (0, 1),
+            (0, 2),
+            (0, 3),
+            (1, 2),
+            (1, 3),
+            (2, 3),
+        ]);
+        let results: Vec<_> = line_graph_automorphisms(&k4)
+            .into_iter()
+            .map(|iso| lift_isomorphism(&k4, &k4, &iso))
+            .collect();
+        assert_eq!(results.len(), 48);
+        assert_eq!(results.iter().filter(|r| r.is_ok()).count(), 24);
+        assert!(results
+            .iter()
+            .all(|r| matches!(r, Ok(_) | Err(LiftError::NotInduced(_)))));
+    }
+
+    #[test]
+    fn invalid() {
+        let g1 = UnGraph::<(), ()>::from_edges([(0, 1), (1, 2)]);
+        let mut g2 = g1.clone();
+        assert_eq!(
+            lift_isomorphism(&g1, &g2, &[0]),
+            Err(LiftError::InvalidMap)
+        );
+        assert_eq!(
+            lift_isomorphism(&g1, &g2, &[1, 1]),
+            Err(LiftError::InvalidMap)
+        );
+        let vertices = lift_isomorphism(&g1, &g2, &[1, 0]).unwrap();
+        assert_eq!(vertices, [2, 1, 0].map(NodeIndex::new));
+        g2.add_node(());
+        assert_eq!(
+            lift_isomorphism(&g1, &g2, &[0, 1]),
+            Err(LiftError::IsolatedVertices)
+        );
+    }
+}